@@ -1,5 +1,6 @@
 pub mod chapter_1 {
     use core::fmt;
+    use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
     fn _debug() {
         // This structure cannot be printed either with `fmt::Display` or
@@ -14,12 +15,262 @@ pub mod chapter_1 {
         // Std library types are automatically printable with {:?}
     }
 
-    #[derive(Debug)]
+    // A closed interval `[min, max]` over `i64`. The fields stay private so the
+    // only way to build one is through `new`, which guarantees `min <= max`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     pub struct MinMax(i64, i64);
 
+    // Returned by `MinMax::new` when the bounds are the wrong way around.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BoundsError {
+        pub min: i64,
+        pub max: i64,
+    }
+
+    impl fmt::Display for BoundsError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "invalid range: min {} is greater than max {}",
+                self.min, self.max
+            )
+        }
+    }
+
+    impl std::error::Error for BoundsError {}
+
+    #[allow(dead_code)]
+    #[allow(clippy::len_without_is_empty)] // a closed interval is never empty
+    impl MinMax {
+        pub fn new(min: i64, max: i64) -> Result<MinMax, BoundsError> {
+            if min <= max {
+                Ok(MinMax(min, max))
+            } else {
+                Err(BoundsError { min, max })
+            }
+        }
+
+        pub fn min(&self) -> i64 {
+            self.0
+        }
+
+        pub fn max(&self) -> i64 {
+            self.1
+        }
+
+        pub fn contains(&self, value: i64) -> bool {
+            self.0 <= value && value <= self.1
+        }
+
+        // Two closed intervals overlap when they share at least one point.
+        pub fn overlaps(&self, other: &MinMax) -> bool {
+            self.0 <= other.1 && other.0 <= self.1
+        }
+
+        pub fn intersection(&self, other: &MinMax) -> Option<MinMax> {
+            MinMax::new(self.0.max(other.0), self.1.min(other.1)).ok()
+        }
+
+        // The smallest interval covering both `self` and `other`.
+        pub fn hull(&self, other: &MinMax) -> MinMax {
+            MinMax(self.0.min(other.0), self.1.max(other.1))
+        }
+
+        // Distance between the bounds, so a degenerate interval has length 0.
+        // `u64` is wide enough for `(i64::MIN, i64::MAX)`.
+        pub fn len(&self) -> u64 {
+            self.1.abs_diff(self.0)
+        }
+
+        pub fn is_degenerate(&self) -> bool {
+            self.0 == self.1
+        }
+
+        pub fn clamp(&self, value: i64) -> i64 {
+            value.clamp(self.0, self.1)
+        }
+
+        // Like `slice::split_at`: the left half ends just before `at` and the
+        // right half starts at `at`. Returns `None` unless `min < at <= max`.
+        pub fn split_at(&self, at: i64) -> Option<(MinMax, MinMax)> {
+            if self.0 < at && at <= self.1 {
+                Some((MinMax(self.0, at - 1), MinMax(at, self.1)))
+            } else {
+                None
+            }
+        }
+    }
+
+    // Interval arithmetic: the result of `a op b` is the smallest interval
+    // holding `x op y` for every `x` in `a` and `y` in `b`. The plain operators
+    // panic on `i64` overflow, like integers do in debug builds; use the
+    // `checked_*` or `saturating_*` methods to handle it instead.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ArithmeticError {
+        Overflow,
+        // The divisor interval contains zero.
+        DivisionByZero,
+    }
+
+    impl fmt::Display for ArithmeticError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ArithmeticError::Overflow => write!(f, "interval arithmetic overflowed i64"),
+                ArithmeticError::DivisionByZero => {
+                    write!(f, "division by an interval containing zero")
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for ArithmeticError {}
+
+    #[allow(dead_code)]
+    impl MinMax {
+        // Applies `op` to the four corner pairs and keeps the extremes. This is
+        // exact for `*` and `/` because both are monotonic in each argument.
+        fn corners(self, rhs: MinMax, op: impl Fn(i64, i64) -> Option<i64>) -> Option<MinMax> {
+            let values = [
+                op(self.0, rhs.0)?,
+                op(self.0, rhs.1)?,
+                op(self.1, rhs.0)?,
+                op(self.1, rhs.1)?,
+            ];
+            let min = values.iter().copied().min()?;
+            let max = values.iter().copied().max()?;
+            Some(MinMax(min, max))
+        }
+
+        pub fn checked_add(self, rhs: MinMax) -> Result<MinMax, ArithmeticError> {
+            match (self.0.checked_add(rhs.0), self.1.checked_add(rhs.1)) {
+                (Some(min), Some(max)) => Ok(MinMax(min, max)),
+                _ => Err(ArithmeticError::Overflow),
+            }
+        }
+
+        pub fn checked_sub(self, rhs: MinMax) -> Result<MinMax, ArithmeticError> {
+            match (self.0.checked_sub(rhs.1), self.1.checked_sub(rhs.0)) {
+                (Some(min), Some(max)) => Ok(MinMax(min, max)),
+                _ => Err(ArithmeticError::Overflow),
+            }
+        }
+
+        pub fn checked_mul(self, rhs: MinMax) -> Result<MinMax, ArithmeticError> {
+            self.corners(rhs, i64::checked_mul)
+                .ok_or(ArithmeticError::Overflow)
+        }
+
+        pub fn checked_neg(self) -> Result<MinMax, ArithmeticError> {
+            match (self.1.checked_neg(), self.0.checked_neg()) {
+                (Some(min), Some(max)) => Ok(MinMax(min, max)),
+                _ => Err(ArithmeticError::Overflow),
+            }
+        }
+
+        // Integer division truncates toward zero, like `i64` division.
+        pub fn checked_div(self, rhs: MinMax) -> Result<MinMax, ArithmeticError> {
+            if rhs.contains(0) {
+                return Err(ArithmeticError::DivisionByZero);
+            }
+            self.corners(rhs, i64::checked_div)
+                .ok_or(ArithmeticError::Overflow)
+        }
+
+        pub fn saturating_add(self, rhs: MinMax) -> MinMax {
+            MinMax(self.0.saturating_add(rhs.0), self.1.saturating_add(rhs.1))
+        }
+
+        pub fn saturating_sub(self, rhs: MinMax) -> MinMax {
+            MinMax(self.0.saturating_sub(rhs.1), self.1.saturating_sub(rhs.0))
+        }
+
+        pub fn saturating_mul(self, rhs: MinMax) -> MinMax {
+            // Saturation is monotonic too, so the corners still bound the result.
+            self.corners(rhs, |a, b| Some(a.saturating_mul(b)))
+                .expect("saturating corners are always defined")
+        }
+
+        pub fn saturating_neg(self) -> MinMax {
+            MinMax(self.1.saturating_neg(), self.0.saturating_neg())
+        }
+
+        // Only `i64::MIN / -1` can overflow, and that saturates to `i64::MAX`.
+        pub fn saturating_div(self, rhs: MinMax) -> Result<MinMax, ArithmeticError> {
+            if rhs.contains(0) {
+                return Err(ArithmeticError::DivisionByZero);
+            }
+            Ok(self
+                .corners(rhs, |a, b| Some(a.saturating_div(b)))
+                .expect("saturating corners are always defined"))
+        }
+    }
+
+    impl Add for MinMax {
+        type Output = MinMax;
+
+        fn add(self, rhs: MinMax) -> MinMax {
+            self.checked_add(rhs).expect("attempt to add with overflow")
+        }
+    }
+
+    impl Sub for MinMax {
+        type Output = MinMax;
+
+        fn sub(self, rhs: MinMax) -> MinMax {
+            self.checked_sub(rhs)
+                .expect("attempt to subtract with overflow")
+        }
+    }
+
+    impl Mul for MinMax {
+        type Output = MinMax;
+
+        fn mul(self, rhs: MinMax) -> MinMax {
+            self.checked_mul(rhs)
+                .expect("attempt to multiply with overflow")
+        }
+    }
+
+    impl Neg for MinMax {
+        type Output = MinMax;
+
+        fn neg(self) -> MinMax {
+            self.checked_neg().expect("attempt to negate with overflow")
+        }
+    }
+
+    // Division can fail on ordinary inputs, so `/` returns the error instead
+    // of panicking.
+    impl Div for MinMax {
+        type Output = Result<MinMax, ArithmeticError>;
+
+        fn div(self, rhs: MinMax) -> Result<MinMax, ArithmeticError> {
+            self.checked_div(rhs)
+        }
+    }
+
+    // Building a `MinMax` from data. `impl FromIterator<i64> for Option<MinMax>`
+    // is rejected by the orphan rule (both `Option` and `i64` are foreign), so
+    // collect into `accumulator::MinMaxOf<i64>` and convert, or use
+    // `MinMax::from_values`.
+    #[allow(dead_code)]
     impl MinMax {
-        pub fn new(x: i64, y: i64) -> MinMax {
-            MinMax(x, y)
+        // `None` when `values` is empty.
+        pub fn from_values<I: IntoIterator<Item = i64>>(values: I) -> Option<MinMax> {
+            values
+                .into_iter()
+                .collect::<accumulator::MinMaxOf<i64>>()
+                .into()
+        }
+    }
+
+    // Widens the range so it also covers every extended value.
+    impl Extend<i64> for MinMax {
+        fn extend<I: IntoIterator<Item = i64>>(&mut self, iter: I) {
+            for value in iter {
+                self.0 = self.0.min(value);
+                self.1 = self.1.max(value);
+            }
         }
     }
 
@@ -33,7 +284,7 @@ pub mod chapter_1 {
     }
 
     // Define a structure where the fields are nameable for comparison.
-    #[derive(Debug)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
     pub struct Point2D {
         x: f64,
         y: f64,
@@ -46,158 +297,7405 @@ pub mod chapter_1 {
         }
     }
 
-    // Similary, implement `Display` for `Point2D`
+    // Treat a `Point2D` as a vector from the origin.
+    #[allow(dead_code)]
+    impl Point2D {
+        pub fn x(&self) -> f64 {
+            self.x
+        }
 
-    impl fmt::Display for Point2D {
-        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            // Customize so only `x` and `y` are denoted.
-            write!(f, "x: {}, y: {}", self.x, self.y)
+        pub fn y(&self) -> f64 {
+            self.y
+        }
+
+        pub fn dot(self, other: Point2D) -> f64 {
+            self.x * other.x + self.y * other.y
+        }
+
+        // The z-component of the 3D cross product. Positive when `other` is
+        // counter-clockwise from `self`.
+        pub fn cross(self, other: Point2D) -> f64 {
+            self.x * other.y - self.y * other.x
+        }
+
+        pub fn norm(self) -> f64 {
+            self.x.hypot(self.y)
+        }
+
+        pub fn distance(self, other: Point2D) -> f64 {
+            (self - other).norm()
+        }
+
+        // Angle from the positive x axis in radians, in `(-π, π]`.
+        pub fn angle(self) -> f64 {
+            self.y.atan2(self.x)
+        }
+
+        // Counter-clockwise rotation about the origin.
+        pub fn rotate(self, radians: f64) -> Point2D {
+            let (sin, cos) = radians.sin_cos();
+            Point2D {
+                x: self.x * cos - self.y * sin,
+                y: self.x * sin + self.y * cos,
+            }
+        }
+
+        // `t = 0` gives `self`, `t = 1` gives `other`.
+        pub fn lerp(self, other: Point2D, t: f64) -> Point2D {
+            self + (other - self) * t
+        }
+
+        // The unit vector in the same direction, or `None` for the zero vector.
+        pub fn normalize(self) -> Option<Point2D> {
+            let norm = self.norm();
+            if norm == 0.0 || !norm.is_finite() {
+                None
+            } else {
+                Some(self / norm)
+            }
+        }
+
+        pub fn approx_eq(self, other: Point2D, epsilon: f64) -> bool {
+            (self.x - other.x).abs() <= epsilon && (self.y - other.y).abs() <= epsilon
         }
     }
 
-    impl fmt::Binary for Point2D {
-        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            // Customize so only `x` and `y` are denoted.
-            write!(f, "x: {:b}, y: {:b}", self.x as i64, self.y as i64)
+    impl Add for Point2D {
+        type Output = Point2D;
+
+        fn add(self, rhs: Point2D) -> Point2D {
+            Point2D::new(self.x + rhs.x, self.y + rhs.y)
         }
     }
 
-    //  Test Case List
-    // Define a structure named `List` containing a `Vec`.
+    impl Sub for Point2D {
+        type Output = Point2D;
 
-    pub struct List(pub Vec<i32>);
+        fn sub(self, rhs: Point2D) -> Point2D {
+            Point2D::new(self.x - rhs.x, self.y - rhs.y)
+        }
+    }
 
-    impl fmt::Display for List {
-        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            // Extract the value using tuple indexing,
-            // and  create a refernce to `vec`.
-            let vec = &self.0;
+    impl Neg for Point2D {
+        type Output = Point2D;
 
-            write!(f, "[")?;
-            // Iterate over `v` in `vec` while enumerating the iteration
-            // count in `cout`
+        fn neg(self) -> Point2D {
+            Point2D::new(-self.x, -self.y)
+        }
+    }
 
-            for (count, v) in vec.iter().enumerate() {
-                // for every element except the first add, a comma.
-                // Use the ? operator to return on erros.
-                if count != 0 {
-                    write!(f, ", ")?;
-                }
-                write!(f, "{}: {}", count, v)?;
-            }
+    impl Mul<f64> for Point2D {
+        type Output = Point2D;
 
-            // Close the oppened bracket and return fmt::Result value
-            write!(f, "]")
+        fn mul(self, rhs: f64) -> Point2D {
+            Point2D::new(self.x * rhs, self.y * rhs)
         }
     }
 
-    pub struct City {
-        pub name: &'static str,
+    impl Mul<Point2D> for f64 {
+        type Output = Point2D;
 
-        // Latitude
-        pub lat: f32,
-        // Longitude
-        pub lon: f32,
+        fn mul(self, rhs: Point2D) -> Point2D {
+            rhs * self
+        }
     }
 
-    impl fmt::Display for City {
-        // `f` is a buffer, and this method must write the formated string int it.
-        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            let lat_c: char = if self.lat >= 0.0 { 'N' } else { 'S' };
-            let lon_c: char = if self.lon >= 0.0 { 'E' } else { 'W' };
+    impl Div<f64> for Point2D {
+        type Output = Point2D;
 
-            // `write` is like `format!`, bit it will write the formatted string
-            // into a biffer (the first argument)
-            write!(f, "{}: {:.3}°{} {:.3}°{}", 
-        self.name, self.lat.abs(), lat_c, self.lon.abs(), lon_c)
+        fn div(self, rhs: f64) -> Point2D {
+            Point2D::new(self.x / rhs, self.y / rhs)
         }
     }
-}
 
-#[cfg(test)]
-mod test {
-    use crate::chapters::chapter_1::chapter_1::City;
+    // Similary, implement `Display` for `Point2D`
 
-    use super::chapter_1::List;
+    impl fmt::Display for Point2D {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            // Customize so only `x` and `y` are denoted. Sign and precision
+            // apply to each coordinate, width and fill to the whole point.
+            let x = signed(f, self.x, |v, p| format!("{:.*}", p, v), |v| v.to_string());
+            let y = signed(f, self.y, |v, p| format!("{:.*}", p, v), |v| v.to_string());
 
-    #[test]
-    fn formart_print() {
-        // In general, the `{}` will be automatically replaced with any
-        // arguments. This will be stringfied.
+            if f.alternate() {
+                // One coordinate per line, each padded on its own.
+                pad(f, &format!("x: {}", x))?;
+                writeln!(f)?;
+                pad(f, &format!("y: {}", y))
+            } else {
+                pad(f, &format!("x: {}, y: {}", x, y))
+            }
+        }
+    }
 
-        let str = format!("{} days", 31);
+    // Formats `value` honoring the `+` flag and precision of `f`.
+    fn signed(
+        f: &fmt::Formatter<'_>,
+        value: f64,
+        with_precision: impl Fn(f64, usize) -> String,
+        without: impl Fn(f64) -> String,
+    ) -> String {
+        let text = match f.precision() {
+            Some(precision) => with_precision(value, precision),
+            None => without(value),
+        };
+        if f.sign_plus() && !text.starts_with('-') {
+            format!("+{}", text)
+        } else {
+            text
+        }
+    }
 
-        assert_eq!("31 days", str);
+    // Like `Formatter::pad`, but never truncates: on numbers precision means
+    // decimal places, not maximum width. Text is left aligned by default.
+    fn pad(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+        pad_aligned(f, text, fmt::Alignment::Left)
+    }
 
-        // Positional arguments can be used . Specfiying an integer `{}`
-        // Determines which additiona; arguments will be replaced. Areguments starts
-        // at 0 immediately adfer the format string.
-        let str = format!("{0}, this is {1}. {1}, this is {0}", "Alice", "Bob");
-        assert_eq!("Alice, this is Bob. Bob, this is Alice", str);
+    // `pad` with the alignment to use when the format string gives none.
+    fn pad_aligned(f: &mut fmt::Formatter<'_>, text: &str, default: fmt::Alignment) -> fmt::Result {
+        let len = text.chars().count();
+        let padding = f.width().unwrap_or(0).saturating_sub(len);
+        let (before, after) = match f.align().unwrap_or(default) {
+            fmt::Alignment::Right => (padding, 0),
+            fmt::Alignment::Center => (padding / 2, padding - padding / 2),
+            fmt::Alignment::Left => (0, padding),
+        };
 
-        // As can named arguments.
-        let str: String = format!(
-            "{subject} {verb} {object}",
-            object = "the lazy dog",
-            subject = "the quick brown fox",
-            verb = "jumps over"
-        );
+        let fill = f.fill();
+        for _ in 0..before {
+            write!(f, "{}", fill)?;
+        }
+        write!(f, "{}", text)?;
+        for _ in 0..after {
+            write!(f, "{}", fill)?;
+        }
+        Ok(())
+    }
 
-        assert_eq!("the quick brown fox jumps over the lazy dog", str);
+    // The radix impls print the raw IEEE-754 bits of each coordinate, zero
+    // padded to the full 64 bits so nothing is lost. The alternate form splits
+    // them into the 1 sign bit, 11 exponent bits and 52 mantissa bits.
+    fn bit_pattern(
+        f: &mut fmt::Formatter<'_>,
+        point: &Point2D,
+        radix: fn(u64, usize) -> String,
+        // Digits needed for all 64 bits, the exponent and the mantissa.
+        widths: [usize; 3],
+    ) -> fmt::Result {
+        let field = |value: f64| {
+            let bits = value.to_bits();
+            if f.alternate() {
+                format!(
+                    "[sign: {}, exponent: {}, mantissa: {}]",
+                    bits >> 63,
+                    radix((bits >> 52) & 0x7ff, widths[1]),
+                    radix(bits & ((1 << 52) - 1), widths[2])
+                )
+            } else {
+                radix(bits, widths[0])
+            }
+        };
+        let text = format!("x: {}, y: {}", field(point.x), field(point.y));
+        pad(f, &text)
+    }
 
-        // different formatting can be invocked by specifying the format character
-        // after a `:`
-        let base_10 = format!("Base 10: {}", 69420);
-        let base_2 = format!("Base 2 (binary): {:b}", 69420);
-        let base_8 = format!("Base 8 (octal): {:o}", 69420);
-        let base_16 = format!("Base 16 (hexadecimal): {:x}", 69420);
-        let base_16_x = format!("Base 16 (hexadecimal): {:X}", 69420);
+    impl fmt::Binary for Point2D {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            bit_pattern(f, self, |v, w| format!("{:0w$b}", v, w = w), [64, 11, 52])
+        }
+    }
 
-        assert_eq!("Base 10: 69420", base_10);
-        assert_eq!("Base 2 (binary): 10000111100101100", base_2);
-        assert_eq!("Base 8 (octal): 207454", base_8);
-        assert_eq!("Base 16 (hexadecimal): 10f2c", base_16);
-        assert_eq!("Base 16 (hexadecimal): 10F2C", base_16_x);
+    impl fmt::Octal for Point2D {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            bit_pattern(f, self, |v, w| format!("{:0w$o}", v, w = w), [22, 4, 18])
+        }
+    }
 
-        // You can right justify text with a specified width. This will
-        // output "    1". (Four white space and a "1", for a total width of 5.)
-        let str = format!("{number:>5}", number = 1);
-        assert_eq!("    1", str);
+    impl fmt::LowerHex for Point2D {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            bit_pattern(f, self, |v, w| format!("{:0w$x}", v, w = w), [16, 3, 13])
+        }
+    }
 
-        // you can pad nmbers with extra zeroes,
-        let str = format!("{number:0>5}", number = 1);
-        assert_eq!("00001", str);
+    impl fmt::UpperHex for Point2D {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            bit_pattern(f, self, |v, w| format!("{:0w$X}", v, w = w), [16, 3, 13])
+        }
+    }
 
-        // You can use named arguments in thr format specifier by appending a '$'
-        let str = format!("{number:0>width$}", number = 1, width = 5);
-        assert_eq!("00001", str);
+    // Scientific notation. The alternate form shows the value as
+    // `mantissa * 2^exponent` with the sign split out, decoded from the bits.
+    fn exponent_form(f: &mut fmt::Formatter<'_>, point: &Point2D, upper: bool) -> fmt::Result {
+        let field = |value: f64| {
+            if f.alternate() {
+                let (sign, exponent, mantissa) = decompose(value);
+                let mantissa = signed(
+                    f,
+                    mantissa,
+                    |v, p| format!("{:.*}", p, v),
+                    |v| v.to_string(),
+                );
+                format!(
+                    "[sign: {}, exponent: {}, mantissa: {}]",
+                    sign, exponent, mantissa
+                )
+            } else if upper {
+                signed(
+                    f,
+                    value,
+                    |v, p| format!("{:.*E}", p, v),
+                    |v| format!("{:E}", v),
+                )
+            } else {
+                signed(
+                    f,
+                    value,
+                    |v, p| format!("{:.*e}", p, v),
+                    |v| format!("{:e}", v),
+                )
+            }
+        };
+        let text = format!("x: {}, y: {}", field(point.x), field(point.y));
+        pad(f, &text)
+    }
 
-        // Rust even checks to make sure the correct number of arguments are used.
-        println!("My name is {0}, {1} {0}", "Bond", "James");
-        let pi = 3.141592;
+    // Splits `value` into its sign, unbiased binary exponent and mantissa,
+    // with the implicit leading 1 restored for normal numbers.
+    fn decompose(value: f64) -> (char, i32, f64) {
+        let bits = value.to_bits();
+        let sign = if bits >> 63 == 1 { '-' } else { '+' };
+        let biased = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = (bits & ((1 << 52) - 1)) as f64 / (1u64 << 52) as f64;
 
-        // Add a println! macro call that prints: Pi is roughly 3.142 by controlling
-        // the number of decimal places shown. For the purposes of this exercise,
-        //use let pi = 3.141592 as an estimate for pi.
-        let str = format!("{pi:.3}");
-        assert_eq!("3.142", str);
+        match biased {
+            // Zero and subnormals have no implicit 1 and a fixed exponent.
+            0 => (sign, -1022, fraction),
+            0x7ff if fraction == 0.0 => (sign, 1024, f64::INFINITY),
+            0x7ff => (sign, 1024, f64::NAN),
+            _ => (sign, biased - 1023, 1.0 + fraction),
+        }
     }
 
-    #[test]
-    fn format_display() {
-        let v: List = List(vec![1, 2, 3]);
-        let str: String = format!("{v}");
-        assert_eq!("[0: 1, 1: 2, 2: 3]", str);
+    impl fmt::LowerExp for Point2D {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            exponent_form(f, self, false)
+        }
     }
 
-    #[test]
-    fn format_specifier() {
-        let city1 = City { name: "Dublin", lat: 53.347778, lon: -6.259722 };
-        let city2 = City { name: "Oslo", lat: 59.95, lon: 10.75 };
-        let city3 = City { name: "Vancouver", lat: 49.25, lon: -123.1 };
+    impl fmt::UpperExp for Point2D {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            exponent_form(f, self, true)
+        }
+    }
 
-        assert_eq!("Dublin: 53.348°N 6.260°W", format!("{}", city1));
-        assert_eq!("Oslo: 59.950°N 10.750°E", format!("{}", city2));
-        assert_eq!("Vancouver: 49.250°N 123.100°W", format!("{}", city3));
+    // The RBE exercise's `Complex`, grown into a usable number type. `Debug`
+    // stays derived, so `{:?}` gives `Complex { real: 3.3, imag: 7.2 }`.
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub struct Complex {
+        pub real: f64,
+        pub imag: f64,
+    }
+
+    #[allow(dead_code)]
+    impl Complex {
+        pub const ZERO: Complex = Complex::new(0.0, 0.0);
+        pub const ONE: Complex = Complex::new(1.0, 0.0);
+        pub const I: Complex = Complex::new(0.0, 1.0);
+
+        pub const fn new(real: f64, imag: f64) -> Complex {
+            Complex { real, imag }
+        }
+
+        pub fn from_polar(modulus: f64, argument: f64) -> Complex {
+            let (sin, cos) = argument.sin_cos();
+            Complex::new(modulus * cos, modulus * sin)
+        }
+
+        // `(modulus, argument)`, the inverse of `from_polar`.
+        pub fn to_polar(self) -> (f64, f64) {
+            (self.modulus(), self.argument())
+        }
+
+        pub fn conj(self) -> Complex {
+            Complex::new(self.real, -self.imag)
+        }
+
+        pub fn modulus(self) -> f64 {
+            self.real.hypot(self.imag)
+        }
+
+        // In `(-π, π]`, like `Point2D::angle`.
+        pub fn argument(self) -> f64 {
+            self.imag.atan2(self.real)
+        }
+
+        pub fn exp(self) -> Complex {
+            Complex::from_polar(self.real.exp(), self.imag)
+        }
+
+        // The principal branch; `ln(0)` is `-inf`, as for `f64`.
+        pub fn ln(self) -> Complex {
+            Complex::new(self.modulus().ln(), self.argument())
+        }
+
+        // Principal value of `self` raised to `exponent`.
+        pub fn powf(self, exponent: f64) -> Complex {
+            if self == Complex::ZERO {
+                // Going through polar form would give `inf * 0 = NaN`.
+                return Complex::new(0f64.powf(exponent), 0.0);
+            }
+            let (modulus, argument) = self.to_polar();
+            Complex::from_polar(modulus.powf(exponent), argument * exponent)
+        }
+
+        pub fn approx_eq(self, other: Complex, epsilon: f64) -> bool {
+            (self.real - other.real).abs() <= epsilon && (self.imag - other.imag).abs() <= epsilon
+        }
+    }
+
+    impl From<f64> for Complex {
+        fn from(real: f64) -> Complex {
+            Complex::new(real, 0.0)
+        }
+    }
+
+    impl Add for Complex {
+        type Output = Complex;
+
+        fn add(self, rhs: Complex) -> Complex {
+            Complex::new(self.real + rhs.real, self.imag + rhs.imag)
+        }
+    }
+
+    impl Sub for Complex {
+        type Output = Complex;
+
+        fn sub(self, rhs: Complex) -> Complex {
+            Complex::new(self.real - rhs.real, self.imag - rhs.imag)
+        }
+    }
+
+    impl Mul for Complex {
+        type Output = Complex;
+
+        fn mul(self, rhs: Complex) -> Complex {
+            Complex::new(
+                self.real * rhs.real - self.imag * rhs.imag,
+                self.real * rhs.imag + self.imag * rhs.real,
+            )
+        }
+    }
+
+    impl Div for Complex {
+        type Output = Complex;
+
+        // Smith's algorithm: scaling by the larger part of `rhs` keeps
+        // `c² + d²` from overflowing for large divisors.
+        fn div(self, rhs: Complex) -> Complex {
+            let (a, b, c, d) = (self.real, self.imag, rhs.real, rhs.imag);
+            if c.abs() >= d.abs() {
+                let ratio = d / c;
+                let denominator = c + d * ratio;
+                Complex::new((a + b * ratio) / denominator, (b - a * ratio) / denominator)
+            } else {
+                let ratio = c / d;
+                let denominator = c * ratio + d;
+                Complex::new((a * ratio + b) / denominator, (b * ratio - a) / denominator)
+            }
+        }
+    }
+
+    impl Neg for Complex {
+        type Output = Complex;
+
+        fn neg(self) -> Complex {
+            Complex::new(-self.real, -self.imag)
+        }
+    }
+
+    // Mixing in plain `f64`s on either side, and the `op=` forms, all go
+    // through the operators above.
+    macro_rules! complex_ops {
+        ($($trait:ident $method:ident $assign_trait:ident $assign_method:ident),*) => {$(
+            impl $trait<f64> for Complex {
+                type Output = Complex;
+
+                fn $method(self, rhs: f64) -> Complex {
+                    $trait::$method(self, Complex::from(rhs))
+                }
+            }
+
+            impl $trait<Complex> for f64 {
+                type Output = Complex;
+
+                fn $method(self, rhs: Complex) -> Complex {
+                    $trait::$method(Complex::from(self), rhs)
+                }
+            }
+
+            impl<T: Into<Complex>> $assign_trait<T> for Complex {
+                fn $assign_method(&mut self, rhs: T) {
+                    *self = $trait::$method(*self, rhs.into());
+                }
+            }
+        )*};
+    }
+
+    complex_ops!(
+        Add add AddAssign add_assign,
+        Sub sub SubAssign sub_assign,
+        Mul mul MulAssign mul_assign,
+        Div div DivAssign div_assign
+    );
+
+    impl fmt::Display for Complex {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            // Sign applies to the real part; the imaginary part always has
+            // one. Precision applies to both, width and fill to the whole.
+            let real = signed(
+                f,
+                self.real,
+                |v, p| format!("{:.*}", p, v),
+                |v| v.to_string(),
+            );
+            let sign = if self.imag.is_sign_negative() && !self.imag.is_nan() {
+                '-'
+            } else {
+                '+'
+            };
+            let imag = match f.precision() {
+                Some(precision) => format!("{:.*}", precision, self.imag.abs()),
+                None => self.imag.abs().to_string(),
+            };
+            pad(f, &format!("{}{}{}i", real, sign, imag))
+        }
+    }
+
+    //  Test Case List
+    // Define a structure named `List` containing a `Vec`.
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct List<T = i32>(pub Vec<T>);
+
+    impl<T: fmt::Display> fmt::Display for List<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            // Extract the value using tuple indexing,
+            // and  create a refernce to `vec`.
+            let vec = &self.0;
+
+            // The loop that writes `[0: 1, 1: 2]` lives in `write_list`, so
+            // `StyledList` can share it.
+            write_list(f, vec, &ListStyle::default())
+        }
+    }
+
+    // How a `List` is laid out. The default gives `[0: 1, 1: 2, 2: 3]`;
+    // each setter returns the style so they can be chained.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ListStyle {
+        separator: String,
+        open: String,
+        close: String,
+        indices: bool,
+        index_base: usize,
+        max_items: Option<usize>,
+    }
+
+    impl Default for ListStyle {
+        fn default() -> Self {
+            ListStyle {
+                separator: ", ".to_owned(),
+                open: "[".to_owned(),
+                close: "]".to_owned(),
+                indices: true,
+                index_base: 0,
+                max_items: None,
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    impl ListStyle {
+        pub fn new() -> ListStyle {
+            ListStyle::default()
+        }
+
+        pub fn separator(mut self, separator: &str) -> ListStyle {
+            self.separator = separator.to_owned();
+            self
+        }
+
+        pub fn brackets(mut self, open: &str, close: &str) -> ListStyle {
+            self.open = open.to_owned();
+            self.close = close.to_owned();
+            self
+        }
+
+        pub fn indices(mut self, show: bool) -> ListStyle {
+            self.indices = show;
+            self
+        }
+
+        // Usually 0 or 1.
+        pub fn index_base(mut self, base: usize) -> ListStyle {
+            self.index_base = base;
+            self
+        }
+
+        // Items past the limit are summarised as `... 998 more`.
+        pub fn max_items(mut self, max: usize) -> ListStyle {
+            self.max_items = Some(max);
+            self
+        }
+    }
+
+    // A `List` paired with a `ListStyle`, ready to be formatted.
+    pub struct StyledList<'a, T> {
+        list: &'a List<T>,
+        style: &'a ListStyle,
+    }
+
+    #[allow(dead_code)]
+    impl<T> List<T> {
+        pub fn styled<'a>(&'a self, style: &'a ListStyle) -> StyledList<'a, T> {
+            StyledList { list: self, style }
+        }
+    }
+
+    impl<T: fmt::Display> fmt::Display for StyledList<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write_list(f, &self.list.0, self.style)
+        }
+    }
+
+    // Format flags are passed on to every item, so `{:.2}` rounds each `f64`.
+    // `{:#}` puts one item per line, indenting whatever the item prints, which
+    // is how nested lists stay readable.
+    fn write_list<T: fmt::Display>(
+        f: &mut fmt::Formatter<'_>,
+        items: &[T],
+        style: &ListStyle,
+    ) -> fmt::Result {
+        const INDENT: &str = "    ";
+
+        let shown = style.max_items.unwrap_or(items.len()).min(items.len());
+        let hidden = items.len() - shown;
+        let pretty = f.alternate() && !items.is_empty();
+
+        write!(f, "{}", style.open)?;
+        // Iterate over `item` in `items` while enumerating the iteration
+        // count in `count`
+        for (count, item) in items[..shown].iter().enumerate() {
+            // for every element except the first add, a separator.
+            // Use the ? operator to return on erros.
+            if pretty {
+                if count != 0 {
+                    write!(f, "{}", style.separator.trim_end())?;
+                }
+                write!(f, "\n{}", INDENT)?;
+            } else if count != 0 {
+                write!(f, "{}", style.separator)?;
+            }
+
+            if style.indices {
+                write!(f, "{}: ", count + style.index_base)?;
+            }
+            if pretty {
+                let text = forward_flags(f, item);
+                write!(f, "{}", text.replace('\n', &format!("\n{}", INDENT)))?;
+            } else {
+                item.fmt(f)?;
+            }
+        }
+
+        if hidden != 0 {
+            if pretty {
+                if shown != 0 {
+                    write!(f, "{}", style.separator.trim_end())?;
+                }
+                write!(f, "\n{}... {} more", INDENT, hidden)?;
+            } else {
+                if shown != 0 {
+                    write!(f, "{}", style.separator)?;
+                }
+                write!(f, "... {} more", hidden)?;
+            }
+        }
+        if pretty {
+            writeln!(f)?;
+        }
+        // Close the oppened bracket and return fmt::Result value
+        write!(f, "{}", style.close)
+    }
+
+    // Formats `item` into a string with the sign, alternate and precision
+    // flags of `f`. Width and fill only make sense on a single line, so they
+    // are left out.
+    fn forward_flags<T: fmt::Display>(f: &fmt::Formatter<'_>, item: &T) -> String {
+        match (f.sign_plus(), f.alternate(), f.precision()) {
+            (false, false, None) => format!("{}", item),
+            (false, false, Some(p)) => format!("{:.*}", p, item),
+            (true, false, None) => format!("{:+}", item),
+            (true, false, Some(p)) => format!("{:+.*}", p, item),
+            (false, true, None) => format!("{:#}", item),
+            (false, true, Some(p)) => format!("{:#.*}", p, item),
+            (true, true, None) => format!("{:+#}", item),
+            (true, true, Some(p)) => format!("{:+#.*}", p, item),
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct City {
+        pub name: &'static str,
+
+        // Latitude
+        pub lat: f32,
+        // Longitude
+        pub lon: f32,
+    }
+
+    impl fmt::Display for City {
+        // `f` is a buffer, and this method must write the formated string int it.
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            // The sign bit rather than `>= 0.0`, so `-0.0` (what "0.000°S"
+            // parses back to) keeps its hemisphere.
+            let lat_c: char = if self.lat.is_sign_negative() {
+                'S'
+            } else {
+                'N'
+            };
+            let lon_c: char = if self.lon.is_sign_negative() {
+                'W'
+            } else {
+                'E'
+            };
+
+            // `write` is like `format!`, bit it will write the formatted string
+            // into a biffer (the first argument)
+            write!(f, "{}: {:.3}°{} {:.3}°{}", 
+        self.name, self.lat.abs(), lat_c, self.lon.abs(), lon_c)
+        }
+    }
+
+    // `City` with an owned name, for cities only known at runtime.
+    #[allow(dead_code)]
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct OwnedCity {
+        pub name: String,
+
+        // Latitude
+        pub lat: f32,
+        // Longitude
+        pub lon: f32,
+    }
+
+    impl From<City> for OwnedCity {
+        fn from(city: City) -> OwnedCity {
+            OwnedCity {
+                name: city.name.to_owned(),
+                lat: city.lat,
+                lon: city.lon,
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    impl OwnedCity {
+        pub fn position(&self) -> geodesy::LatLon {
+            geodesy::LatLon {
+                lat: self.lat as f64,
+                lon: self.lon as f64,
+            }
+        }
+    }
+
+    // Same output as `City`.
+    impl fmt::Display for OwnedCity {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}: {}", self.name, self.position())
+        }
+    }
+
+    // `FromStr` for every type above, accepting exactly what its `Display`
+    // writes so that `s.parse::<T>()?.to_string() == s`.
+    #[allow(dead_code)]
+    pub mod parse {
+        use core::fmt;
+        use std::str::FromStr;
+
+        use super::{City, Complex, List, MinMax, OwnedCity, Point2D};
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Expected {
+            // A fixed piece of text such as `", "` or `"°"`.
+            Text(&'static str),
+            OneOf(&'static [&'static str]),
+            Integer,
+            Number,
+            UnsignedNumber,
+            // The running position in a `List`.
+            Index(usize),
+            Name,
+            // A `MinMax` whose first bound is not above the second.
+            OrderedBounds,
+            // A value that parsed but is out of range, described in words.
+            Value(&'static str),
+            End,
+        }
+
+        impl fmt::Display for Expected {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    Expected::Text(text) => write!(f, "`{}`", text),
+                    Expected::OneOf(options) => {
+                        for (count, text) in options.iter().enumerate() {
+                            if count != 0 {
+                                write!(f, " or ")?;
+                            }
+                            write!(f, "`{}`", text)?;
+                        }
+                        Ok(())
+                    }
+                    Expected::Integer => write!(f, "an integer"),
+                    Expected::Number => write!(f, "a number"),
+                    Expected::UnsignedNumber => write!(f, "an unsigned number"),
+                    Expected::Index(index) => write!(f, "index {}", index),
+                    Expected::Name => write!(f, "a name"),
+                    Expected::OrderedBounds => write!(f, "min <= max"),
+                    Expected::Value(description) => write!(f, "{}", description),
+                    Expected::End => write!(f, "end of input"),
+                }
+            }
+        }
+
+        // `offset` is the byte position in the input where `expected` failed.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct ParseError {
+            pub offset: usize,
+            pub expected: Expected,
+        }
+
+        impl fmt::Display for ParseError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "expected {} at byte {}", self.expected, self.offset)
+            }
+        }
+
+        impl std::error::Error for ParseError {}
+
+        // Walks the input left to right, tracking the byte offset for errors.
+        pub(super) struct Cursor<'a> {
+            input: &'a str,
+            pub(super) pos: usize,
+        }
+
+        impl<'a> Cursor<'a> {
+            pub(super) fn new(input: &'a str) -> Cursor<'a> {
+                Cursor { input, pos: 0 }
+            }
+
+            pub(super) fn rest(&self) -> &'a str {
+                &self.input[self.pos..]
+            }
+
+            pub(super) fn error(&self, expected: Expected) -> ParseError {
+                ParseError {
+                    offset: self.pos,
+                    expected,
+                }
+            }
+
+            pub(super) fn eat(&mut self, text: &str) -> bool {
+                if self.rest().starts_with(text) {
+                    self.pos += text.len();
+                    true
+                } else {
+                    false
+                }
+            }
+
+            pub(super) fn text(&mut self, text: &'static str) -> Result<(), ParseError> {
+                if self.eat(text) {
+                    Ok(())
+                } else {
+                    Err(self.error(Expected::Text(text)))
+                }
+            }
+
+            // Returns the index of whichever option matched.
+            pub(super) fn one_of(
+                &mut self,
+                options: &'static [&'static str],
+            ) -> Result<usize, ParseError> {
+                match options
+                    .iter()
+                    .position(|text| self.rest().starts_with(text))
+                {
+                    Some(index) => {
+                        self.pos += options[index].len();
+                        Ok(index)
+                    }
+                    None => Err(self.error(Expected::OneOf(options))),
+                }
+            }
+
+            pub(super) fn skip_whitespace(&mut self) {
+                let rest = self.rest();
+                self.pos += rest.len() - rest.trim_start().len();
+            }
+
+            pub(super) fn end(&self) -> Result<(), ParseError> {
+                if self.rest().is_empty() {
+                    Ok(())
+                } else {
+                    Err(self.error(Expected::End))
+                }
+            }
+
+            // Consumes the longest prefix whose chars satisfy `accept` and
+            // parses it, leaving the cursor untouched on failure.
+            pub(super) fn token<T: FromStr>(
+                &mut self,
+                accept: impl Fn(char) -> bool,
+                expected: Expected,
+            ) -> Result<T, ParseError> {
+                let len = self
+                    .rest()
+                    .find(|c: char| !accept(c))
+                    .unwrap_or(self.rest().len());
+                let value = self.rest()[..len]
+                    .parse()
+                    .map_err(|_| self.error(expected))?;
+                self.pos += len;
+                Ok(value)
+            }
+
+            pub(super) fn integer<T: FromStr>(&mut self) -> Result<T, ParseError> {
+                self.token(|c| c == '-' || c.is_ascii_digit(), Expected::Integer)
+            }
+
+            // Floats are printed with `{}`, which may also write `inf` or `NaN`.
+            pub(super) fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+                self.token(
+                    |c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '+',
+                    Expected::Number,
+                )
+            }
+        }
+
+        // "(0, 14)"
+        impl FromStr for MinMax {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<MinMax, ParseError> {
+                let mut cursor = Cursor::new(s);
+                cursor.text("(")?;
+                let start = cursor.pos;
+                let min = cursor.integer()?;
+                cursor.text(", ")?;
+                let max = cursor.integer()?;
+                cursor.text(")")?;
+                cursor.end()?;
+
+                MinMax::new(min, max).map_err(|_| ParseError {
+                    offset: start,
+                    expected: Expected::OrderedBounds,
+                })
+            }
+        }
+
+        // "x: 4.5, y: 6.8"
+        impl FromStr for Point2D {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Point2D, ParseError> {
+                let mut cursor = Cursor::new(s);
+                cursor.text("x: ")?;
+                let x = cursor.number()?;
+                cursor.text(", y: ")?;
+                let y = cursor.number()?;
+                cursor.end()?;
+
+                Ok(Point2D { x, y })
+            }
+        }
+
+        // "3.3+7.2i", "-1.5-2i", "4", "-i", "2.5e-3i"
+        impl FromStr for Complex {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Complex, ParseError> {
+                let mut cursor = Cursor::new(s);
+                let sign = complex_sign(&mut cursor);
+                let first = unsigned_float(&mut cursor)?;
+                if cursor.eat("i") {
+                    cursor.end()?;
+                    // A bare `i` has an implied coefficient of one.
+                    return Ok(Complex::new(0.0, sign * first.unwrap_or(1.0)));
+                }
+                let real = sign * first.ok_or_else(|| cursor.error(Expected::Number))?;
+                if cursor.rest().is_empty() {
+                    return Ok(Complex::from(real));
+                }
+
+                let sign = match cursor.one_of(&["+", "-"]) {
+                    Ok(index) => [1.0, -1.0][index],
+                    Err(_) => return Err(cursor.error(Expected::OneOf(&["+", "-", "i"]))),
+                };
+                let imag = sign * unsigned_float(&mut cursor)?.unwrap_or(1.0);
+                cursor.text("i")?;
+                cursor.end()?;
+                Ok(Complex::new(real, imag))
+            }
+        }
+
+        // An optional leading `+` or `-`, as a factor.
+        fn complex_sign(cursor: &mut Cursor<'_>) -> f64 {
+            if cursor.eat("-") {
+                -1.0
+            } else {
+                cursor.eat("+");
+                1.0
+            }
+        }
+
+        // `None` when there is no number at all, as in front of a bare `i`.
+        fn unsigned_float(cursor: &mut Cursor<'_>) -> Result<Option<f64>, ParseError> {
+            let rest = cursor.rest();
+            let len = ["inf", "NaN"]
+                .iter()
+                .find(|word| rest.starts_with(*word))
+                .map_or_else(|| float_len(rest), |word| word.len());
+            if len == 0 {
+                return Ok(None);
+            }
+
+            let value = rest[..len]
+                .parse()
+                .map_err(|_| cursor.error(Expected::UnsignedNumber))?;
+            cursor.pos += len;
+            Ok(Some(value))
+        }
+
+        // Length of the `1.5` or `2.5e-3` at the start of `text`, without
+        // a leading sign.
+        fn float_len(text: &str) -> usize {
+            let bytes = text.as_bytes();
+            let digits = |from: usize| {
+                from + bytes[from..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_digit())
+                    .count()
+            };
+
+            let mut len = digits(0);
+            if bytes.get(len) == Some(&b'.') {
+                len = digits(len + 1);
+            }
+            if len == 0 || text[..len] == *"." {
+                return 0;
+            }
+            if matches!(bytes.get(len), Some(b'e' | b'E')) {
+                let sign = usize::from(matches!(bytes.get(len + 1), Some(b'+' | b'-')));
+                let end = digits(len + 1 + sign);
+                if end > len + 1 + sign {
+                    len = end;
+                }
+            }
+            len
+        }
+
+        // "[0: 1, 1: 2, 2: 3]"
+        impl FromStr for List {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<List, ParseError> {
+                let mut cursor = Cursor::new(s);
+                let mut vec = Vec::new();
+                cursor.text("[")?;
+
+                if !cursor.eat("]") {
+                    loop {
+                        let start = cursor.pos;
+                        let index: usize = cursor.integer()?;
+                        if index != vec.len() {
+                            return Err(ParseError {
+                                offset: start,
+                                expected: Expected::Index(vec.len()),
+                            });
+                        }
+                        cursor.text(": ")?;
+                        vec.push(cursor.integer()?);
+
+                        if cursor.one_of(&[", ", "]"])? == 1 {
+                            break;
+                        }
+                    }
+                }
+                cursor.end()?;
+
+                Ok(List(vec))
+            }
+        }
+
+        // "Dublin: 53.348°N 6.260°W"
+        //
+        // `City` borrows its name for `'static`, so the parsed name has to be
+        // leaked. Each successful parse leaks one small allocation; parse an
+        // `OwnedCity` to avoid that.
+        impl FromStr for City {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<City, ParseError> {
+                let (name, lat, lon) = city_parts(s)?;
+                Ok(City {
+                    name: Box::leak(name.to_owned().into_boxed_str()),
+                    lat,
+                    lon,
+                })
+            }
+        }
+
+        impl FromStr for OwnedCity {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<OwnedCity, ParseError> {
+                let (name, lat, lon) = city_parts(s)?;
+                Ok(OwnedCity {
+                    name: name.to_owned(),
+                    lat,
+                    lon,
+                })
+            }
+        }
+
+        fn city_parts(s: &str) -> Result<(&str, f32, f32), ParseError> {
+            // Names may contain `: ` themselves, so split on the last one.
+            let name_len = match s.rfind(": ") {
+                Some(0) | None => {
+                    return Err(ParseError {
+                        offset: 0,
+                        expected: Expected::Name,
+                    })
+                }
+                Some(len) => len,
+            };
+
+            let mut cursor = Cursor::new(s);
+            cursor.pos = name_len;
+            cursor.text(": ")?;
+            let lat = unsigned(&mut cursor, 90.0)?;
+            cursor.text("°")?;
+            let lat_sign = if cursor.one_of(&["N", "S"])? == 0 {
+                1.0
+            } else {
+                -1.0
+            };
+            cursor.text(" ")?;
+            let lon = unsigned(&mut cursor, 180.0)?;
+            cursor.text("°")?;
+            let lon_sign = if cursor.one_of(&["E", "W"])? == 0 {
+                1.0
+            } else {
+                -1.0
+            };
+            cursor.end()?;
+
+            Ok((&s[..name_len], lat * lat_sign, lon * lon_sign))
+        }
+
+        // `City` prints magnitudes and carries the sign in the hemisphere.
+        // Only what `Display` could have written is accepted, so no NaN,
+        // infinity or angle past the pole or the antimeridian.
+        fn unsigned(cursor: &mut Cursor<'_>, limit: f32) -> Result<f32, ParseError> {
+            let start = cursor.pos;
+            let value: f32 = cursor.number()?;
+            let expected = if value.is_sign_negative() {
+                Expected::UnsignedNumber
+            } else if !value.is_finite() || value > limit {
+                if limit == 90.0 {
+                    Expected::Value("a latitude within ±90°")
+                } else {
+                    Expected::Value("a longitude within ±180°")
+                }
+            } else {
+                return Ok(value);
+            };
+            Err(ParseError {
+                offset: start,
+                expected,
+            })
+        }
+    }
+
+    // Great-circle and ellipsoidal calculations between `City` coordinates.
+    // Spherical formulas use the mean Earth radius; `vincenty_distance` uses
+    // the WGS-84 ellipsoid and is accurate to well under a metre.
+    #[allow(dead_code)]
+    pub mod geodesy {
+        use core::fmt;
+
+        use super::City;
+
+        pub const EARTH_RADIUS_KM: f64 = 6371.0;
+
+        const WGS84_A: f64 = 6_378_137.0;
+        const WGS84_F: f64 = 1.0 / 298.257_223_563;
+
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+        pub struct Kilometres(pub f64);
+
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+        pub struct Metres(pub f64);
+
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+        pub struct NauticalMiles(pub f64);
+
+        impl From<Metres> for Kilometres {
+            fn from(m: Metres) -> Kilometres {
+                Kilometres(m.0 / 1000.0)
+            }
+        }
+
+        impl From<Kilometres> for Metres {
+            fn from(km: Kilometres) -> Metres {
+                Metres(km.0 * 1000.0)
+            }
+        }
+
+        // A nautical mile is exactly 1852 m.
+        impl From<Kilometres> for NauticalMiles {
+            fn from(km: Kilometres) -> NauticalMiles {
+                NauticalMiles(km.0 / 1.852)
+            }
+        }
+
+        impl From<NauticalMiles> for Kilometres {
+            fn from(nm: NauticalMiles) -> Kilometres {
+                Kilometres(nm.0 * 1.852)
+            }
+        }
+
+        impl From<Metres> for NauticalMiles {
+            fn from(m: Metres) -> NauticalMiles {
+                NauticalMiles(m.0 / 1852.0)
+            }
+        }
+
+        impl From<NauticalMiles> for Metres {
+            fn from(nm: NauticalMiles) -> Metres {
+                Metres(nm.0 * 1852.0)
+            }
+        }
+
+        // `{:.1}` gives `1267.0 km`; the precision applies to the number.
+        fn unit(f: &mut fmt::Formatter<'_>, value: f64, suffix: &str) -> fmt::Result {
+            match f.precision() {
+                Some(precision) => write!(f, "{:.*} {}", precision, value, suffix),
+                None => write!(f, "{} {}", value, suffix),
+            }
+        }
+
+        impl fmt::Display for Kilometres {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                unit(f, self.0, "km")
+            }
+        }
+
+        impl fmt::Display for Metres {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                unit(f, self.0, "m")
+            }
+        }
+
+        impl fmt::Display for NauticalMiles {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                unit(f, self.0, "NM")
+            }
+        }
+
+        // An unnamed position in degrees, for results that aren't cities.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct LatLon {
+            pub lat: f64,
+            pub lon: f64,
+        }
+
+        // Same layout as `City`, without the name: `53.348°N 6.260°W`.
+        impl fmt::Display for LatLon {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                // As for `City`, `-0.0` stays in the southern and western
+                // hemispheres so it survives a round trip.
+                let lat_c: char = if self.lat.is_sign_negative() {
+                    'S'
+                } else {
+                    'N'
+                };
+                let lon_c: char = if self.lon.is_sign_negative() {
+                    'W'
+                } else {
+                    'E'
+                };
+                write!(
+                    f,
+                    "{:.3}°{} {:.3}°{}",
+                    self.lat.abs(),
+                    lat_c,
+                    self.lon.abs(),
+                    lon_c
+                )
+            }
+        }
+
+        impl LatLon {
+            // Great-circle distance on a sphere of `EARTH_RADIUS_KM`.
+            pub fn distance(&self, other: &LatLon) -> Kilometres {
+                Kilometres(self.angle_to(other) * EARTH_RADIUS_KM)
+            }
+
+            fn radians(&self) -> (f64, f64) {
+                (self.lat.to_radians(), self.lon.to_radians())
+            }
+
+            fn from_radians(lat: f64, lon: f64) -> LatLon {
+                LatLon {
+                    lat: lat.to_degrees(),
+                    lon: normalize_lon(lon.to_degrees()),
+                }
+            }
+
+            // Central angle between the two points in radians (haversine).
+            fn angle_to(&self, other: &LatLon) -> f64 {
+                let (lat1, lon1) = self.radians();
+                let (lat2, lon2) = other.radians();
+                let h = ((lat2 - lat1) / 2.0).sin().powi(2)
+                    + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+                2.0 * h.sqrt().min(1.0).asin()
+            }
+
+            fn bearing_to(&self, other: &LatLon) -> f64 {
+                let (lat1, lon1) = self.radians();
+                let (lat2, lon2) = other.radians();
+                let y = (lon2 - lon1).sin() * lat2.cos();
+                let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * (lon2 - lon1).cos();
+                y.atan2(x).to_degrees().rem_euclid(360.0)
+            }
+        }
+
+        // Wraps a longitude into `[-180, 180)`.
+        fn normalize_lon(lon: f64) -> f64 {
+            (lon + 540.0).rem_euclid(360.0) - 180.0
+        }
+
+        impl City {
+            pub fn position(&self) -> LatLon {
+                LatLon {
+                    lat: self.lat as f64,
+                    lon: self.lon as f64,
+                }
+            }
+
+            // Great-circle distance on a sphere of `EARTH_RADIUS_KM`.
+            pub fn haversine_distance(&self, other: &City) -> Kilometres {
+                self.position().distance(&other.position())
+            }
+
+            // Distance on the WGS-84 ellipsoid. `None` when the iteration does
+            // not converge, which only happens for nearly antipodal points.
+            pub fn vincenty_distance(&self, other: &City) -> Option<Metres> {
+                let b = (1.0 - WGS84_F) * WGS84_A;
+                let (lat1, lon1) = self.position().radians();
+                let (lat2, lon2) = other.position().radians();
+
+                let l = lon2 - lon1;
+                let (sin_u1, cos_u1) = ((1.0 - WGS84_F) * lat1.tan()).atan().sin_cos();
+                let (sin_u2, cos_u2) = ((1.0 - WGS84_F) * lat2.tan()).atan().sin_cos();
+
+                let mut lambda = l;
+                for _ in 0..200 {
+                    let (sin_lambda, cos_lambda) = lambda.sin_cos();
+                    let sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+                        + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+                    .sqrt();
+                    if sin_sigma == 0.0 {
+                        // Coincident points.
+                        return Some(Metres(0.0));
+                    }
+                    let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+                    let sigma = sin_sigma.atan2(cos_sigma);
+                    let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+                    let cos2_alpha = 1.0 - sin_alpha * sin_alpha;
+                    // Both points on the equator.
+                    let cos_2sigma_m = if cos2_alpha == 0.0 {
+                        0.0
+                    } else {
+                        cos_sigma - 2.0 * sin_u1 * sin_u2 / cos2_alpha
+                    };
+                    let c =
+                        WGS84_F / 16.0 * cos2_alpha * (4.0 + WGS84_F * (4.0 - 3.0 * cos2_alpha));
+
+                    let previous = lambda;
+                    lambda = l
+                        + (1.0 - c)
+                            * WGS84_F
+                            * sin_alpha
+                            * (sigma
+                                + c * sin_sigma
+                                    * (cos_2sigma_m
+                                        + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))));
+
+                    if (lambda - previous).abs() < 1e-12 {
+                        let u2 = cos2_alpha * (WGS84_A.powi(2) - b.powi(2)) / b.powi(2);
+                        let a = 1.0
+                            + u2 / 16384.0 * (4096.0 + u2 * (-768.0 + u2 * (320.0 - 175.0 * u2)));
+                        let b_coef =
+                            u2 / 1024.0 * (256.0 + u2 * (-128.0 + u2 * (74.0 - 47.0 * u2)));
+                        let delta_sigma = b_coef
+                            * sin_sigma
+                            * (cos_2sigma_m
+                                + b_coef / 4.0
+                                    * (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))
+                                        - b_coef / 6.0
+                                            * cos_2sigma_m
+                                            * (-3.0 + 4.0 * sin_sigma.powi(2))
+                                            * (-3.0 + 4.0 * cos_2sigma_m.powi(2))));
+                        return Some(Metres(b * a * (sigma - delta_sigma)));
+                    }
+                }
+                None
+            }
+
+            // Bearing in degrees clockwise from north, in `[0, 360)`, when
+            // setting off towards `other`.
+            pub fn initial_bearing(&self, other: &City) -> f64 {
+                self.position().bearing_to(&other.position())
+            }
+
+            // Bearing on arrival at `other`, which differs from the initial
+            // bearing because great circles are not rhumb lines.
+            pub fn final_bearing(&self, other: &City) -> f64 {
+                (other.position().bearing_to(&self.position()) + 180.0).rem_euclid(360.0)
+            }
+
+            // Halfway point along the great circle.
+            pub fn midpoint(&self, other: &City) -> LatLon {
+                let (lat1, lon1) = self.position().radians();
+                let (lat2, lon2) = other.position().radians();
+                let bx = lat2.cos() * (lon2 - lon1).cos();
+                let by = lat2.cos() * (lon2 - lon1).sin();
+
+                let lat =
+                    (lat1.sin() + lat2.sin()).atan2(((lat1.cos() + bx).powi(2) + by * by).sqrt());
+                let lon = lon1 + by.atan2(lat1.cos() + bx);
+                LatLon::from_radians(lat, lon)
+            }
+
+            // Where you end up after travelling `distance` along a great circle
+            // starting on `bearing` degrees.
+            pub fn destination(&self, bearing: f64, distance: Kilometres) -> LatLon {
+                let (lat1, lon1) = self.position().radians();
+                let (sin_d, cos_d) = (distance.0 / EARTH_RADIUS_KM).sin_cos();
+                let (sin_b, cos_b) = bearing.to_radians().sin_cos();
+
+                let lat2 = (lat1.sin() * cos_d + lat1.cos() * sin_d * cos_b).asin();
+                let lon2 =
+                    lon1 + (sin_b * sin_d * lat1.cos()).atan2(cos_d - lat1.sin() * lat2.sin());
+                LatLon::from_radians(lat2, lon2)
+            }
+
+            // Distance from `self` to the great circle through `start` and
+            // `end`. Positive when `self` is to the right of the route.
+            pub fn cross_track_distance(&self, start: &City, end: &City) -> Kilometres {
+                let start = start.position();
+                let here = self.position();
+                let angle = start.angle_to(&here);
+                let theta =
+                    (start.bearing_to(&here) - start.bearing_to(&end.position())).to_radians();
+                Kilometres((angle.sin() * theta.sin()).asin() * EARTH_RADIUS_KM)
+            }
+        }
+    }
+
+    // Alternative ways to write a position, each with a matching parser.
+    // Displaying a position and parsing it back in the same notation and
+    // precision is exact; otherwise the error is at most half of the last
+    // printed unit. The default precisions below are all about 1-3 m apart on
+    // the ground, and `{:.N}` asks for more digits.
+    #[allow(dead_code)]
+    pub mod notation {
+        use core::fmt;
+        use std::str::FromStr;
+
+        use super::geodesy::LatLon;
+        use super::parse::{Cursor, Expected, ParseError};
+        use super::{City, OwnedCity};
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Notation {
+            // `53.348°N 6.260°W`, the `City` default. 3 decimals.
+            Decimal,
+            // `53.347778, -6.259722`. 6 decimals.
+            SignedDecimal,
+            // `53°20'52"N 6°15'35"W`. Whole seconds.
+            Dms,
+            // `53°20.867'N 6°15.583'W`. 3 decimals of a minute.
+            DegreesMinutes,
+            // `29U 682400 5914460`, zone and band, easting, northing. Whole
+            // metres. Only defined between 80°S and 84°N.
+            Utm,
+        }
+
+        impl Notation {
+            pub const ALL: [Notation; 5] = [
+                Notation::Decimal,
+                Notation::SignedDecimal,
+                Notation::Dms,
+                Notation::DegreesMinutes,
+                Notation::Utm,
+            ];
+
+            fn default_precision(self) -> usize {
+                match self {
+                    Notation::Decimal | Notation::DegreesMinutes => 3,
+                    Notation::SignedDecimal => 6,
+                    Notation::Dms | Notation::Utm => 0,
+                }
+            }
+
+            pub fn parse(self, s: &str) -> Result<LatLon, ParseError> {
+                let mut cursor = Cursor::new(s);
+                cursor.skip_whitespace();
+                let position = match self {
+                    Notation::SignedDecimal => signed_decimal(&mut cursor)?,
+                    Notation::Utm => utm(&mut cursor)?.to_lat_lon(),
+                    _ => {
+                        let lat = angle(&mut cursor, self, &["N", "S"], 90.0)?;
+                        cursor.skip_whitespace();
+                        let lon = angle(&mut cursor, self, &["E", "W"], 180.0)?;
+                        LatLon { lat, lon }
+                    }
+                };
+                cursor.skip_whitespace();
+                cursor.end()?;
+                Ok(position)
+            }
+        }
+
+        // Accepts any notation, reporting the error from whichever parser
+        // got furthest when none of them match.
+        impl FromStr for LatLon {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<LatLon, ParseError> {
+                let mut furthest: Option<ParseError> = None;
+                for notation in Notation::ALL {
+                    match notation.parse(s) {
+                        Ok(position) => return Ok(position),
+                        Err(err) if furthest.is_none_or(|f| err.offset > f.offset) => {
+                            furthest = Some(err)
+                        }
+                        Err(_) => {}
+                    }
+                }
+                Err(furthest.expect("at least one notation was tried"))
+            }
+        }
+
+        // A position rendered in a chosen notation, optionally prefixed with
+        // a name like `City` does.
+        pub struct Notated<'a> {
+            name: Option<&'a str>,
+            position: LatLon,
+            notation: Notation,
+        }
+
+        impl LatLon {
+            pub fn notated(&self, notation: Notation) -> Notated<'static> {
+                Notated {
+                    name: None,
+                    position: *self,
+                    notation,
+                }
+            }
+        }
+
+        impl City {
+            pub fn notated(&self, notation: Notation) -> Notated<'_> {
+                Notated {
+                    name: Some(self.name),
+                    position: self.position(),
+                    notation,
+                }
+            }
+        }
+
+        impl OwnedCity {
+            pub fn notated(&self, notation: Notation) -> Notated<'_> {
+                Notated {
+                    name: Some(&self.name),
+                    position: self.position(),
+                    notation,
+                }
+            }
+        }
+
+        impl fmt::Display for Notated<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                if let Some(name) = self.name {
+                    write!(f, "{}: ", name)?;
+                }
+                let precision = f.precision().unwrap_or(self.notation.default_precision());
+                let LatLon { lat, lon } = self.position;
+
+                match self.notation {
+                    Notation::SignedDecimal => {
+                        write!(f, "{:.*}, {:.*}", precision, lat, precision, lon)
+                    }
+                    Notation::Utm => match self.position.to_utm() {
+                        Some(utm) => write!(f, "{:.*}", precision, utm),
+                        None => write!(f, "outside UTM coverage"),
+                    },
+                    notation => {
+                        write_angle(f, lat, notation, precision, ['N', 'S'])?;
+                        write!(f, " ")?;
+                        write_angle(f, lon, notation, precision, ['E', 'W'])
+                    }
+                }
+            }
+        }
+
+        fn write_angle(
+            f: &mut fmt::Formatter<'_>,
+            degrees: f64,
+            notation: Notation,
+            precision: usize,
+            hemispheres: [char; 2],
+        ) -> fmt::Result {
+            // Round once in the smallest printed unit so that e.g. 59.9996"
+            // carries into the minutes instead of printing as 60".
+            let scale = 10f64.powi(precision as i32);
+            let unit = match notation {
+                Notation::Dms => 3600.0,
+                Notation::DegreesMinutes => 60.0,
+                _ => 1.0,
+            };
+            let total = (degrees.abs() * unit * scale).round();
+            // The sign bit, as in `City`'s `Display`, so a value that rounds
+            // to zero keeps its hemisphere and `-0.0` reads back as itself.
+            let hemisphere = if degrees.is_sign_negative() {
+                hemispheres[1]
+            } else {
+                hemispheres[0]
+            };
+
+            match notation {
+                Notation::Dms => {
+                    let per_degree = 3600.0 * scale;
+                    let d = (total / per_degree).floor();
+                    let m = ((total - d * per_degree) / (60.0 * scale)).floor();
+                    let s = (total - d * per_degree - m * 60.0 * scale) / scale;
+                    write!(f, "{}°{}'{:.*}\"{}", d, m, precision, s, hemisphere)
+                }
+                Notation::DegreesMinutes => {
+                    let d = (total / (60.0 * scale)).floor();
+                    let m = (total - d * 60.0 * scale) / scale;
+                    write!(f, "{}°{:.*}'{}", d, precision, m, hemisphere)
+                }
+                _ => write!(f, "{:.*}°{}", precision, degrees.abs(), hemisphere),
+            }
+        }
+
+        fn signed_decimal(cursor: &mut Cursor<'_>) -> Result<LatLon, ParseError> {
+            let lat = bounded(cursor, 90.0)?;
+            cursor.skip_whitespace();
+            cursor.text(",")?;
+            cursor.skip_whitespace();
+            let lon = bounded(cursor, 180.0)?;
+            Ok(LatLon { lat, lon })
+        }
+
+        fn bounded(cursor: &mut Cursor<'_>, limit: f64) -> Result<f64, ParseError> {
+            let start = cursor.pos;
+            let value: f64 = cursor.number()?;
+            if value.abs() <= limit {
+                Ok(value)
+            } else {
+                cursor.pos = start;
+                Err(cursor.error(out_of_range(limit)))
+            }
+        }
+
+        fn out_of_range(limit: f64) -> Expected {
+            if limit == 90.0 {
+                Expected::Value("a latitude within ±90°")
+            } else {
+                Expected::Value("a longitude within ±180°")
+            }
+        }
+
+        // One hemisphere-suffixed angle in `Decimal`, `Dms` or
+        // `DegreesMinutes` form, returned as signed degrees.
+        fn angle(
+            cursor: &mut Cursor<'_>,
+            notation: Notation,
+            hemispheres: &'static [&'static str],
+            limit: f64,
+        ) -> Result<f64, ParseError> {
+            let start = cursor.pos;
+            // Whitespace is allowed between every component of pasted input.
+            let degrees = match notation {
+                Notation::Dms => {
+                    let d: u32 = cursor.integer()?;
+                    cursor.text("°")?;
+                    cursor.skip_whitespace();
+                    let m = sexagesimal(cursor, true)?;
+                    cursor.text("'")?;
+                    cursor.skip_whitespace();
+                    let s = sexagesimal(cursor, false)?;
+                    cursor.text("\"")?;
+                    cursor.skip_whitespace();
+                    d as f64 + m / 60.0 + s / 3600.0
+                }
+                Notation::DegreesMinutes => {
+                    let d: u32 = cursor.integer()?;
+                    cursor.text("°")?;
+                    cursor.skip_whitespace();
+                    let m = sexagesimal(cursor, false)?;
+                    cursor.text("'")?;
+                    cursor.skip_whitespace();
+                    d as f64 + m / 60.0
+                }
+                _ => {
+                    let start = cursor.pos;
+                    let d: f64 = cursor.number()?;
+                    if d.is_sign_negative() {
+                        cursor.pos = start;
+                        return Err(cursor.error(Expected::UnsignedNumber));
+                    }
+                    cursor.text("°")?;
+                    cursor.skip_whitespace();
+                    d
+                }
+            };
+            let sign = if cursor.one_of(hemispheres)? == 0 {
+                1.0
+            } else {
+                -1.0
+            };
+
+            // NaN compares false with everything, so test finiteness too.
+            if !degrees.is_finite() || degrees > limit {
+                cursor.pos = start;
+                return Err(cursor.error(out_of_range(limit)));
+            }
+            Ok(sign * degrees)
+        }
+
+        // Minutes or seconds: below 60, and whole when `integer` is set.
+        fn sexagesimal(cursor: &mut Cursor<'_>, integer: bool) -> Result<f64, ParseError> {
+            let start = cursor.pos;
+            let value: f64 = if integer {
+                cursor.integer::<u32>()? as f64
+            } else {
+                cursor.number()?
+            };
+            if (0.0..60.0).contains(&value) {
+                Ok(value)
+            } else {
+                cursor.pos = start;
+                Err(cursor.error(Expected::Value("a value from 0 to 59")))
+            }
+        }
+
+        fn utm(cursor: &mut Cursor<'_>) -> Result<Utm, ParseError> {
+            let start = cursor.pos;
+            let zone: u8 = cursor.integer()?;
+            let band = cursor.rest().chars().next().unwrap_or(' ');
+            if !(1..=60).contains(&zone) || !BANDS.contains(band) {
+                cursor.pos = start;
+                return Err(cursor.error(Expected::Value("a UTM zone and band like 29U")));
+            }
+            cursor.pos += band.len_utf8();
+            cursor.skip_whitespace();
+            let easting = cursor.number()?;
+            cursor.skip_whitespace();
+            let northing = cursor.number()?;
+
+            Ok(Utm {
+                zone,
+                band,
+                easting,
+                northing,
+            })
+        }
+
+        // Latitude bands from 80°S, 8° each; `X` is stretched to 84°N.
+        const BANDS: &str = "CDEFGHJKLMNPQRSTUVWX";
+
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct Utm {
+            pub zone: u8,
+            pub band: char,
+            // Metres east of the zone's false origin.
+            pub easting: f64,
+            // Metres north of the equator, or of 10 000 km south of it in the
+            // southern hemisphere.
+            pub northing: f64,
+        }
+
+        // `29U 682400 5914460`. Precision applies to the metres.
+        impl fmt::Display for Utm {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let precision = f.precision().unwrap_or(0);
+                write!(
+                    f,
+                    "{}{} {:.*} {:.*}",
+                    self.zone, self.band, precision, self.easting, precision, self.northing
+                )
+            }
+        }
+
+        impl FromStr for Utm {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Utm, ParseError> {
+                let mut cursor = Cursor::new(s);
+                let utm = utm(&mut cursor)?;
+                cursor.end()?;
+                Ok(utm)
+            }
+        }
+
+        // WGS-84 with the UTM scale factor on the central meridian.
+        const A: f64 = 6_378_137.0;
+        const F: f64 = 1.0 / 298.257_223_563;
+        const K0: f64 = 0.9996;
+        const FALSE_EASTING: f64 = 500_000.0;
+        const FALSE_NORTHING: f64 = 10_000_000.0;
+
+        // Krüger series to sixth order in `n`, accurate to a few nanometres
+        // inside a zone (Karney, "Transverse Mercator with an accuracy of a
+        // few nanometers", 2011).
+        struct Series {
+            e: f64,
+            a: f64,
+            alpha: [f64; 6],
+            beta: [f64; 6],
+        }
+
+        fn series() -> Series {
+            let n = F / (2.0 - F);
+            let (n2, n3, n4, n5, n6) = (n.powi(2), n.powi(3), n.powi(4), n.powi(5), n.powi(6));
+            Series {
+                e: (F * (2.0 - F)).sqrt(),
+                a: A / (1.0 + n) * (1.0 + n2 / 4.0 + n4 / 64.0 + n6 / 256.0),
+                alpha: [
+                    n / 2.0 - 2.0 / 3.0 * n2 + 5.0 / 16.0 * n3 + 41.0 / 180.0 * n4
+                        - 127.0 / 288.0 * n5
+                        + 7891.0 / 37800.0 * n6,
+                    13.0 / 48.0 * n2 - 3.0 / 5.0 * n3 + 557.0 / 1440.0 * n4 + 281.0 / 630.0 * n5
+                        - 1983433.0 / 1935360.0 * n6,
+                    61.0 / 240.0 * n3 - 103.0 / 140.0 * n4
+                        + 15061.0 / 26880.0 * n5
+                        + 167603.0 / 181440.0 * n6,
+                    49561.0 / 161280.0 * n4 - 179.0 / 168.0 * n5 + 6601661.0 / 7257600.0 * n6,
+                    34729.0 / 80640.0 * n5 - 3418889.0 / 1995840.0 * n6,
+                    212378941.0 / 319334400.0 * n6,
+                ],
+                beta: [
+                    n / 2.0 - 2.0 / 3.0 * n2 + 37.0 / 96.0 * n3
+                        - 1.0 / 360.0 * n4
+                        - 81.0 / 512.0 * n5
+                        + 96199.0 / 604800.0 * n6,
+                    1.0 / 48.0 * n2 + 1.0 / 15.0 * n3 - 437.0 / 1440.0 * n4 + 46.0 / 105.0 * n5
+                        - 1118711.0 / 3870720.0 * n6,
+                    17.0 / 480.0 * n3 - 37.0 / 840.0 * n4 - 209.0 / 4480.0 * n5
+                        + 5569.0 / 90720.0 * n6,
+                    4397.0 / 161280.0 * n4 - 11.0 / 504.0 * n5 - 830251.0 / 7257600.0 * n6,
+                    4583.0 / 161280.0 * n5 - 108847.0 / 3991680.0 * n6,
+                    20648693.0 / 638668800.0 * n6,
+                ],
+            }
+        }
+
+        fn central_meridian(zone: u8) -> f64 {
+            ((zone as f64 - 1.0) * 6.0 - 180.0 + 3.0).to_radians()
+        }
+
+        // Conformal latitude helper shared by both directions.
+        fn tau_prime(tau: f64, e: f64) -> f64 {
+            let sigma = (e * (e * tau / (1.0 + tau * tau).sqrt()).atanh()).sinh();
+            tau * (1.0 + sigma * sigma).sqrt() - sigma * (1.0 + tau * tau).sqrt()
+        }
+
+        impl LatLon {
+            // `None` outside 80°S..=84°N, where polar stereographic is used
+            // instead.
+            pub fn to_utm(self) -> Option<Utm> {
+                if !(-80.0..=84.0).contains(&self.lat) {
+                    return None;
+                }
+                let lon = (self.lon + 540.0).rem_euclid(360.0) - 180.0;
+                let mut zone = (((lon + 180.0) / 6.0).floor() as u8 + 1).min(60);
+                let band_index = (((self.lat + 80.0) / 8.0).floor() as usize).min(BANDS.len() - 1);
+                let band = BANDS.as_bytes()[band_index] as char;
+
+                // Norway and Svalbard get wider or shifted zones.
+                if band == 'V' && zone == 31 && lon >= 3.0 {
+                    zone = 32;
+                }
+                if band == 'X' {
+                    zone = match zone {
+                        32 if lon < 9.0 => 31,
+                        32 => 33,
+                        34 if lon < 21.0 => 33,
+                        34 => 35,
+                        36 if lon < 33.0 => 35,
+                        36 => 37,
+                        zone => zone,
+                    };
+                }
+
+                let series = series();
+                let phi = self.lat.to_radians();
+                let lambda = lon.to_radians() - central_meridian(zone);
+                let tau_p = tau_prime(phi.tan(), series.e);
+                let xi_p = tau_p.atan2(lambda.cos());
+                let eta_p = (lambda.sin() / (tau_p * tau_p + lambda.cos().powi(2)).sqrt()).asinh();
+
+                let mut xi = xi_p;
+                let mut eta = eta_p;
+                for (j, alpha) in series.alpha.iter().enumerate() {
+                    let k = 2.0 * (j + 1) as f64;
+                    xi += alpha * (k * xi_p).sin() * (k * eta_p).cosh();
+                    eta += alpha * (k * xi_p).cos() * (k * eta_p).sinh();
+                }
+
+                let easting = K0 * series.a * eta + FALSE_EASTING;
+                let mut northing = K0 * series.a * xi;
+                if self.lat < 0.0 {
+                    northing += FALSE_NORTHING;
+                }
+
+                Some(Utm {
+                    zone,
+                    band,
+                    easting,
+                    northing,
+                })
+            }
+        }
+
+        impl Utm {
+            pub fn to_lat_lon(self) -> LatLon {
+                let series = series();
+                let mut northing = self.northing;
+                if self.band < 'N' {
+                    northing -= FALSE_NORTHING;
+                }
+                let xi = northing / (K0 * series.a);
+                let eta = (self.easting - FALSE_EASTING) / (K0 * series.a);
+
+                let mut xi_p = xi;
+                let mut eta_p = eta;
+                for (j, beta) in series.beta.iter().enumerate() {
+                    let k = 2.0 * (j + 1) as f64;
+                    xi_p -= beta * (k * xi).sin() * (k * eta).cosh();
+                    eta_p -= beta * (k * xi).cos() * (k * eta).sinh();
+                }
+
+                let sinh_eta_p = eta_p.sinh();
+                let (sin_xi_p, cos_xi_p) = xi_p.sin_cos();
+                let tau_p = sin_xi_p / (sinh_eta_p * sinh_eta_p + cos_xi_p * cos_xi_p).sqrt();
+
+                // Newton-Raphson for the geodetic `tan(latitude)`.
+                let e2 = series.e * series.e;
+                let mut tau = tau_p;
+                for _ in 0..16 {
+                    let tau_i_p = tau_prime(tau, series.e);
+                    let delta = (tau_p - tau_i_p) / (1.0 + tau_i_p * tau_i_p).sqrt()
+                        * (1.0 + (1.0 - e2) * tau * tau)
+                        / ((1.0 - e2) * (1.0 + tau * tau).sqrt());
+                    tau += delta;
+                    if delta.abs() < 1e-12 {
+                        break;
+                    }
+                }
+
+                let lon = sinh_eta_p.atan2(cos_xi_p) + central_meridian(self.zone);
+                LatLon {
+                    lat: tau.atan().to_degrees(),
+                    lon: (lon.to_degrees() + 540.0).rem_euclid(360.0) - 180.0,
+                }
+            }
+        }
+    }
+
+    // A searchable collection of `OwnedCity` loaded from CSV. Names are
+    // matched case- and accent-insensitively, so "zurich" finds "Zürich".
+    #[allow(dead_code)]
+    pub mod gazetteer {
+        use core::cmp::Ordering;
+        use core::fmt;
+        use std::collections::{BinaryHeap, HashMap};
+        use std::fs::File;
+        use std::io::{self, BufRead, BufReader};
+        use std::path::Path;
+
+        use super::geodesy::{Kilometres, LatLon};
+        use super::OwnedCity;
+
+        // Why a CSV row was skipped. `line` is 1-based.
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct RowError {
+            pub line: usize,
+            pub kind: RowErrorKind,
+        }
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum RowErrorKind {
+            FieldCount(usize),
+            UnterminatedQuote,
+            EmptyName,
+            Latitude(String),
+            Longitude(String),
+        }
+
+        impl fmt::Display for RowError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "line {}: ", self.line)?;
+                match &self.kind {
+                    RowErrorKind::FieldCount(count) => {
+                        write!(f, "expected 3 fields (name,lat,lon), found {}", count)
+                    }
+                    RowErrorKind::UnterminatedQuote => write!(f, "unterminated quoted field"),
+                    RowErrorKind::EmptyName => write!(f, "empty city name"),
+                    RowErrorKind::Latitude(text) => {
+                        write!(f, "latitude `{}` is not a number in [-90, 90]", text)
+                    }
+                    RowErrorKind::Longitude(text) => {
+                        write!(f, "longitude `{}` is not a number in [-180, 180]", text)
+                    }
+                }
+            }
+        }
+
+        impl std::error::Error for RowError {}
+
+        // `west > east` describes a box that crosses the antimeridian.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct BoundingBox {
+            pub south: f64,
+            pub west: f64,
+            pub north: f64,
+            pub east: f64,
+        }
+
+        impl BoundingBox {
+            pub fn contains(&self, point: &LatLon) -> bool {
+                let lon_ok = if self.west <= self.east {
+                    self.west <= point.lon && point.lon <= self.east
+                } else {
+                    point.lon >= self.west || point.lon <= self.east
+                };
+                self.south <= point.lat && point.lat <= self.north && lon_ok
+            }
+        }
+
+        #[derive(Debug, Default)]
+        pub struct Gazetteer {
+            cities: Vec<OwnedCity>,
+            // Folded name -> every city with that name.
+            by_name: HashMap<String, Vec<usize>>,
+            // Folded names in sorted order, for prefix search.
+            sorted_names: Vec<(String, usize)>,
+            // City indices sorted by latitude, for bounding boxes.
+            by_lat: Vec<usize>,
+            // Implicit k-d tree over unit-sphere coordinates: the median of
+            // each slice is the node, the halves either side its children.
+            tree: Vec<usize>,
+            points: Vec<[f64; 3]>,
+        }
+
+        impl Gazetteer {
+            pub fn new(cities: Vec<OwnedCity>) -> Gazetteer {
+                let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+                let mut sorted_names = Vec::with_capacity(cities.len());
+                for (index, city) in cities.iter().enumerate() {
+                    let folded = fold(&city.name);
+                    by_name.entry(folded.clone()).or_default().push(index);
+                    sorted_names.push((folded, index));
+                }
+                sorted_names.sort();
+
+                let mut by_lat: Vec<usize> = (0..cities.len()).collect();
+                by_lat.sort_by(|&a, &b| cities[a].lat.total_cmp(&cities[b].lat));
+
+                let points: Vec<[f64; 3]> =
+                    cities.iter().map(|c| unit_vector(&c.position())).collect();
+                let mut tree: Vec<usize> = (0..cities.len()).collect();
+                build(&mut tree, &points, 0);
+
+                Gazetteer {
+                    cities,
+                    by_name,
+                    sorted_names,
+                    by_lat,
+                    tree,
+                    points,
+                }
+            }
+
+            // Loads `name,lat,lon` rows, with an optional header line. Rows
+            // that fail to parse are skipped and returned alongside the
+            // gazetteer; only I/O errors abort the load.
+            pub fn from_csv<R: BufRead>(reader: R) -> io::Result<(Gazetteer, Vec<RowError>)> {
+                let mut cities = Vec::new();
+                let mut errors = Vec::new();
+
+                for (index, line) in reader.lines().enumerate() {
+                    let line = line?;
+                    let number = index + 1;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    match parse_row(&line) {
+                        Ok(city) => cities.push(city),
+                        Err(_) if number == 1 && is_header(&line) => {}
+                        Err(kind) => errors.push(RowError { line: number, kind }),
+                    }
+                }
+
+                Ok((Gazetteer::new(cities), errors))
+            }
+
+            pub fn from_csv_path<P: AsRef<Path>>(
+                path: P,
+            ) -> io::Result<(Gazetteer, Vec<RowError>)> {
+                Gazetteer::from_csv(BufReader::new(File::open(path)?))
+            }
+
+            pub fn len(&self) -> usize {
+                self.cities.len()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.cities.is_empty()
+            }
+
+            pub fn cities(&self) -> &[OwnedCity] {
+                &self.cities
+            }
+
+            // Every city whose name matches, ignoring case and accents.
+            pub fn lookup(&self, name: &str) -> Vec<&OwnedCity> {
+                self.by_name
+                    .get(&fold(name))
+                    .map(|indices| indices.iter().map(|&i| &self.cities[i]).collect())
+                    .unwrap_or_default()
+            }
+
+            // Up to `limit` cities whose name starts with `prefix`, in
+            // alphabetical order of their folded names.
+            pub fn autocomplete(&self, prefix: &str, limit: usize) -> Vec<&OwnedCity> {
+                let prefix = fold(prefix);
+                let start = self
+                    .sorted_names
+                    .partition_point(|(name, _)| name.as_str() < prefix.as_str());
+                self.sorted_names[start..]
+                    .iter()
+                    .take_while(|(name, _)| name.starts_with(&prefix))
+                    .take(limit)
+                    .map(|&(_, i)| &self.cities[i])
+                    .collect()
+            }
+
+            pub fn within(&self, bounds: &BoundingBox) -> Vec<&OwnedCity> {
+                let start = self
+                    .by_lat
+                    .partition_point(|&i| (self.cities[i].lat as f64) < bounds.south);
+                self.by_lat[start..]
+                    .iter()
+                    .map(|&i| &self.cities[i])
+                    .take_while(|city| city.lat as f64 <= bounds.north)
+                    .filter(|city| bounds.contains(&city.position()))
+                    .collect()
+            }
+
+            // The `k` cities closest to `target` by great-circle distance,
+            // nearest first.
+            pub fn nearest(&self, target: &LatLon, k: usize) -> Vec<(&OwnedCity, Kilometres)> {
+                if k == 0 {
+                    return Vec::new();
+                }
+                let query = unit_vector(target);
+                let mut heap = BinaryHeap::with_capacity(k + 1);
+                self.search(0, self.tree.len(), 0, &query, k, &mut heap);
+
+                let mut found: Vec<_> = heap
+                    .into_iter()
+                    .map(|Candidate(_, i)| {
+                        let city = &self.cities[i];
+                        (city, target.distance(&city.position()))
+                    })
+                    .collect();
+                found.sort_by(|a, b| a.1 .0.total_cmp(&b.1 .0));
+                found
+            }
+
+            // Chord length grows with great-circle distance, so the nearest
+            // neighbours in 3D are also the nearest on the sphere.
+            fn search(
+                &self,
+                lo: usize,
+                hi: usize,
+                depth: usize,
+                query: &[f64; 3],
+                k: usize,
+                heap: &mut BinaryHeap<Candidate>,
+            ) {
+                if lo >= hi {
+                    return;
+                }
+                let mid = (lo + hi) / 2;
+                let index = self.tree[mid];
+                let point = &self.points[index];
+
+                heap.push(Candidate(squared_distance(point, query), index));
+                if heap.len() > k {
+                    heap.pop();
+                }
+
+                let axis = depth % 3;
+                let diff = query[axis] - point[axis];
+                let (near, far) = if diff < 0.0 {
+                    ((lo, mid), (mid + 1, hi))
+                } else {
+                    ((mid + 1, hi), (lo, mid))
+                };
+
+                self.search(near.0, near.1, depth + 1, query, k, heap);
+                // Only cross the splitting plane if it is closer than the
+                // worst candidate so far.
+                let worst = heap.peek().map_or(f64::INFINITY, |c| c.0);
+                if heap.len() < k || diff * diff < worst {
+                    self.search(far.0, far.1, depth + 1, query, k, heap);
+                }
+            }
+        }
+
+        // Max-heap entry keyed on squared chord distance.
+        struct Candidate(f64, usize);
+
+        impl PartialEq for Candidate {
+            fn eq(&self, other: &Candidate) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl Eq for Candidate {}
+
+        impl PartialOrd for Candidate {
+            fn partial_cmp(&self, other: &Candidate) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for Candidate {
+            fn cmp(&self, other: &Candidate) -> Ordering {
+                self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+            }
+        }
+
+        fn build(indices: &mut [usize], points: &[[f64; 3]], depth: usize) {
+            if indices.len() <= 1 {
+                return;
+            }
+            let axis = depth % 3;
+            let mid = indices.len() / 2;
+            indices
+                .select_nth_unstable_by(mid, |&a, &b| points[a][axis].total_cmp(&points[b][axis]));
+
+            let (left, right) = indices.split_at_mut(mid);
+            build(left, points, depth + 1);
+            build(&mut right[1..], points, depth + 1);
+        }
+
+        fn unit_vector(position: &LatLon) -> [f64; 3] {
+            let (lat, lon) = (position.lat.to_radians(), position.lon.to_radians());
+            [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+        }
+
+        fn squared_distance(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+            (0..3).map(|i| (a[i] - b[i]).powi(2)).sum()
+        }
+
+        fn is_header(line: &str) -> bool {
+            split_fields(line)
+                .ok()
+                .and_then(|fields| {
+                    fields
+                        .first()
+                        .map(|f| f.trim().eq_ignore_ascii_case("name"))
+                })
+                .unwrap_or(false)
+        }
+
+        fn parse_row(line: &str) -> Result<OwnedCity, RowErrorKind> {
+            let fields = split_fields(line)?;
+            if fields.len() != 3 {
+                return Err(RowErrorKind::FieldCount(fields.len()));
+            }
+
+            let name = fields[0].trim();
+            if name.is_empty() {
+                return Err(RowErrorKind::EmptyName);
+            }
+            let lat = coordinate(&fields[1], 90.0)
+                .ok_or_else(|| RowErrorKind::Latitude(fields[1].clone()))?;
+            let lon = coordinate(&fields[2], 180.0)
+                .ok_or_else(|| RowErrorKind::Longitude(fields[2].clone()))?;
+
+            Ok(OwnedCity {
+                name: name.to_owned(),
+                lat,
+                lon,
+            })
+        }
+
+        fn coordinate(text: &str, limit: f32) -> Option<f32> {
+            text.trim()
+                .parse::<f32>()
+                .ok()
+                .filter(|value| value.abs() <= limit)
+        }
+
+        // Splits one CSV line on commas. Fields may be wrapped in double
+        // quotes, with `""` standing for a literal quote inside them.
+        fn split_fields(line: &str) -> Result<Vec<String>, RowErrorKind> {
+            let mut fields = Vec::new();
+            let mut field = String::new();
+            let mut chars = line.chars().peekable();
+            let mut quoted = false;
+
+            while let Some(c) = chars.next() {
+                match (c, quoted) {
+                    ('"', true) if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    ('"', true) => quoted = false,
+                    ('"', false) if field.trim().is_empty() => {
+                        field.clear();
+                        quoted = true;
+                    }
+                    (',', false) => fields.push(std::mem::take(&mut field)),
+                    _ => field.push(c),
+                }
+            }
+            if quoted {
+                return Err(RowErrorKind::UnterminatedQuote);
+            }
+            fields.push(field);
+            Ok(fields)
+        }
+
+        // Lowercases `name` and strips diacritics from Latin letters.
+        pub fn fold(name: &str) -> String {
+            const TABLE: &[(&str, &str)] = &[
+                ("àáâãäåāăą", "a"),
+                ("çćĉċč", "c"),
+                ("ďđð", "d"),
+                ("èéêëēĕėęě", "e"),
+                ("ĝğġģ", "g"),
+                ("ĥħ", "h"),
+                ("ìíîïĩīĭįı", "i"),
+                ("ĵ", "j"),
+                ("ķ", "k"),
+                ("ĺļľŀł", "l"),
+                ("ñńņňŉ", "n"),
+                ("òóôõöøōŏő", "o"),
+                ("ŕŗř", "r"),
+                ("śŝşšș", "s"),
+                ("ţťŧț", "t"),
+                ("ùúûüũūŭůűų", "u"),
+                ("ŵ", "w"),
+                ("ýÿŷ", "y"),
+                ("źżž", "z"),
+                ("æ", "ae"),
+                ("œ", "oe"),
+                ("ß", "ss"),
+                ("þ", "th"),
+            ];
+
+            let mut folded = String::with_capacity(name.len());
+            for c in name.chars().flat_map(char::to_lowercase) {
+                // Combining marks, as found in decomposed text.
+                if ('\u{300}'..='\u{36f}').contains(&c) {
+                    continue;
+                }
+                match TABLE.iter().find(|(from, _)| from.contains(c)) {
+                    Some((_, to)) => folded.push_str(to),
+                    None => folded.push(c),
+                }
+            }
+            folded
+        }
+    }
+
+    // Geohashes: a position encoded as a base-32 string in which every extra
+    // character narrows the cell, so nearby places share a prefix.
+    #[allow(dead_code)]
+    pub mod geohash {
+        use core::fmt;
+        use std::collections::BTreeMap;
+        use std::str::FromStr;
+
+        use super::gazetteer::BoundingBox;
+        use super::geodesy::LatLon;
+        use super::{City, OwnedCity};
+
+        const BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+        // Twelve characters is 60 bits, cells of a few centimetres.
+        pub const MAX_PRECISION: usize = 12;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum GeohashError {
+            Precision(usize),
+            // A character outside the geohash alphabet at byte `offset`.
+            Char { offset: usize, found: char },
+        }
+
+        impl fmt::Display for GeohashError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    GeohashError::Precision(precision) => write!(
+                        f,
+                        "geohash precision must be 1 to {}, not {}",
+                        MAX_PRECISION, precision
+                    ),
+                    GeohashError::Char { offset, found } => {
+                        write!(
+                            f,
+                            "invalid geohash character `{}` at byte {}",
+                            found, offset
+                        )
+                    }
+                }
+            }
+        }
+
+        impl std::error::Error for GeohashError {}
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Direction {
+            North,
+            NorthEast,
+            East,
+            SouthEast,
+            South,
+            SouthWest,
+            West,
+            NorthWest,
+        }
+
+        impl Direction {
+            pub const ALL: [Direction; 8] = [
+                Direction::North,
+                Direction::NorthEast,
+                Direction::East,
+                Direction::SouthEast,
+                Direction::South,
+                Direction::SouthWest,
+                Direction::West,
+                Direction::NorthWest,
+            ];
+
+            // Steps in cells as `(north, east)`.
+            fn offset(self) -> (f64, f64) {
+                match self {
+                    Direction::North => (1.0, 0.0),
+                    Direction::NorthEast => (1.0, 1.0),
+                    Direction::East => (0.0, 1.0),
+                    Direction::SouthEast => (-1.0, 1.0),
+                    Direction::South => (-1.0, 0.0),
+                    Direction::SouthWest => (-1.0, -1.0),
+                    Direction::West => (0.0, -1.0),
+                    Direction::NorthWest => (1.0, -1.0),
+                }
+            }
+        }
+
+        // Ordered so that a cell sorts right before everything inside it.
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct Geohash(String);
+
+        impl Geohash {
+            pub fn encode(position: LatLon, precision: usize) -> Result<Geohash, GeohashError> {
+                if !(1..=MAX_PRECISION).contains(&precision) {
+                    return Err(GeohashError::Precision(precision));
+                }
+
+                let (mut lat, mut lon) = ((-90.0, 90.0), (-180.0, 180.0));
+                let mut hash = String::with_capacity(precision);
+                // Five bits make one character.
+                let (mut value, mut bits) = (0, 0);
+                let mut even = true;
+
+                while hash.len() < precision {
+                    // Bits alternate, starting with longitude.
+                    let (range, coordinate) = if even {
+                        (&mut lon, position.lon)
+                    } else {
+                        (&mut lat, position.lat)
+                    };
+                    let mid = (range.0 + range.1) / 2.0;
+                    value <<= 1;
+                    if coordinate >= mid {
+                        value |= 1;
+                        range.0 = mid;
+                    } else {
+                        range.1 = mid;
+                    }
+                    even = !even;
+
+                    bits += 1;
+                    if bits == 5 {
+                        hash.push(BASE32[value] as char);
+                        (value, bits) = (0, 0);
+                    }
+                }
+
+                Ok(Geohash(hash))
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn precision(&self) -> usize {
+                self.0.len()
+            }
+
+            // The enclosing cell one character shorter.
+            pub fn parent(&self) -> Option<Geohash> {
+                match self.0.len() {
+                    0 | 1 => None,
+                    len => Some(Geohash(self.0[..len - 1].to_owned())),
+                }
+            }
+
+            // Whether `other` lies inside this cell.
+            pub fn contains(&self, other: &Geohash) -> bool {
+                other.0.starts_with(&self.0)
+            }
+
+            pub fn decode(&self) -> Cell {
+                let (mut lat, mut lon) = ((-90.0, 90.0), (-180.0, 180.0));
+                let mut even = true;
+
+                for byte in self.0.bytes() {
+                    let value = BASE32
+                        .iter()
+                        .position(|&b| b == byte)
+                        .expect("validated on construction");
+                    for shift in (0..5).rev() {
+                        let range = if even { &mut lon } else { &mut lat };
+                        let mid = (range.0 + range.1) / 2.0;
+                        if value >> shift & 1 == 1 {
+                            range.0 = mid;
+                        } else {
+                            range.1 = mid;
+                        }
+                        even = !even;
+                    }
+                }
+
+                Cell {
+                    hash: self.clone(),
+                    bounds: BoundingBox {
+                        south: lat.0,
+                        west: lon.0,
+                        north: lat.1,
+                        east: lon.1,
+                    },
+                }
+            }
+
+            // The adjacent cell of the same precision. Longitude wraps around
+            // the antimeridian; there is nothing north of the north pole.
+            pub fn neighbor(&self, direction: Direction) -> Option<Geohash> {
+                let cell = self.decode();
+                let b = cell.bounds;
+                let (north, east) = direction.offset();
+                let lat = cell.centre().lat + north * (b.north - b.south);
+                let lon = cell.centre().lon + east * (b.east - b.west);
+                if !(-90.0..=90.0).contains(&lat) {
+                    return None;
+                }
+                let lon = (lon + 540.0).rem_euclid(360.0) - 180.0;
+                Geohash::encode(LatLon { lat, lon }, self.precision()).ok()
+            }
+
+            pub fn neighbors(&self) -> Vec<(Direction, Geohash)> {
+                Direction::ALL
+                    .into_iter()
+                    .filter_map(|d| self.neighbor(d).map(|hash| (d, hash)))
+                    .collect()
+            }
+        }
+
+        impl FromStr for Geohash {
+            type Err = GeohashError;
+
+            fn from_str(s: &str) -> Result<Geohash, GeohashError> {
+                if let Some((offset, found)) = s
+                    .char_indices()
+                    .find(|&(_, c)| !c.is_ascii() || !BASE32.contains(&(c as u8)))
+                {
+                    return Err(GeohashError::Char { offset, found });
+                }
+                if !(1..=MAX_PRECISION).contains(&s.len()) {
+                    return Err(GeohashError::Precision(s.len()));
+                }
+                Ok(Geohash(s.to_owned()))
+            }
+        }
+
+        impl fmt::Display for Geohash {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.pad(&self.0)
+            }
+        }
+
+        // The area a geohash stands for.
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Cell {
+            pub hash: Geohash,
+            pub bounds: BoundingBox,
+        }
+
+        impl Cell {
+            pub fn centre(&self) -> LatLon {
+                LatLon {
+                    lat: (self.bounds.south + self.bounds.north) / 2.0,
+                    lon: (self.bounds.west + self.bounds.east) / 2.0,
+                }
+            }
+
+            pub fn south_west(&self) -> LatLon {
+                LatLon {
+                    lat: self.bounds.south,
+                    lon: self.bounds.west,
+                }
+            }
+
+            pub fn north_east(&self) -> LatLon {
+                LatLon {
+                    lat: self.bounds.north,
+                    lon: self.bounds.east,
+                }
+            }
+        }
+
+        // `gc7x9: 53.306°N 6.284°W to 53.350°N 6.240°W`, corners in the same
+        // style as `City`.
+        impl fmt::Display for Cell {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "{}: {} to {}",
+                    self.hash,
+                    self.south_west(),
+                    self.north_east()
+                )
+            }
+        }
+
+        impl City {
+            pub fn geohash(&self, precision: usize) -> Result<Geohash, GeohashError> {
+                Geohash::encode(self.position(), precision)
+            }
+        }
+
+        impl OwnedCity {
+            pub fn geohash(&self, precision: usize) -> Result<Geohash, GeohashError> {
+                Geohash::encode(self.position(), precision)
+            }
+        }
+
+        // Buckets `items` by the geohash cell of `precision` characters that
+        // their position falls in.
+        pub fn group_by_cell<T>(
+            items: impl IntoIterator<Item = T>,
+            precision: usize,
+            position: impl Fn(&T) -> LatLon,
+        ) -> Result<BTreeMap<Geohash, Vec<T>>, GeohashError> {
+            let mut groups: BTreeMap<Geohash, Vec<T>> = BTreeMap::new();
+            for item in items {
+                let hash = Geohash::encode(position(&item), precision)?;
+                groups.entry(hash).or_default().push(item);
+            }
+            Ok(groups)
+        }
+    }
+
+    // A streaming min/max over any `PartialOrd` type that also remembers where
+    // the extremes were seen. Partial accumulators over consecutive chunks can
+    // be combined with `merge`, so a long slice can be reduced in parallel.
+    #[allow(dead_code)]
+    pub mod accumulator {
+        use super::MinMax;
+
+        // What to do with values that are unordered even with themselves,
+        // i.e. `NaN` for floats.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+        pub enum NanPolicy {
+            // Skip them; they still count towards the indices.
+            #[default]
+            Ignore,
+            // The first one seen becomes both the minimum and the maximum,
+            // the way `NaN` poisons float arithmetic.
+            Propagate,
+        }
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct MinMaxOf<T> {
+            // `(index, value)` pairs.
+            min: Option<(usize, T)>,
+            max: Option<(usize, T)>,
+            nan: Option<(usize, T)>,
+            count: usize,
+            policy: NanPolicy,
+        }
+
+        impl<T> Default for MinMaxOf<T> {
+            fn default() -> Self {
+                MinMaxOf::with_nan_policy(NanPolicy::default())
+            }
+        }
+
+        impl<T> MinMaxOf<T> {
+            pub fn new() -> MinMaxOf<T> {
+                MinMaxOf::default()
+            }
+
+            pub fn with_nan_policy(policy: NanPolicy) -> MinMaxOf<T> {
+                MinMaxOf {
+                    min: None,
+                    max: None,
+                    nan: None,
+                    count: 0,
+                    policy,
+                }
+            }
+
+            // Number of values pushed so far, including skipped `NaN`s.
+            pub fn count(&self) -> usize {
+                self.count
+            }
+
+            fn poisoned(&self) -> Option<&(usize, T)> {
+                match self.policy {
+                    NanPolicy::Ignore => None,
+                    NanPolicy::Propagate => self.nan.as_ref(),
+                }
+            }
+
+            pub fn min(&self) -> Option<&T> {
+                self.poisoned().or(self.min.as_ref()).map(|(_, v)| v)
+            }
+
+            pub fn max(&self) -> Option<&T> {
+                self.poisoned().or(self.max.as_ref()).map(|(_, v)| v)
+            }
+
+            // Index of the first occurrence of the minimum.
+            pub fn argmin(&self) -> Option<usize> {
+                self.poisoned().or(self.min.as_ref()).map(|(i, _)| *i)
+            }
+
+            // Index of the first occurrence of the maximum.
+            pub fn argmax(&self) -> Option<usize> {
+                self.poisoned().or(self.max.as_ref()).map(|(i, _)| *i)
+            }
+        }
+
+        impl<T: PartialOrd + Clone> MinMaxOf<T> {
+            pub fn push(&mut self, value: T) {
+                let index = self.count;
+                self.count += 1;
+                self.observe(index, value);
+            }
+
+            fn observe(&mut self, index: usize, value: T) {
+                if value.partial_cmp(&value).is_none() {
+                    if self.nan.is_none() {
+                        self.nan = Some((index, value));
+                    }
+                    return;
+                }
+
+                // Strict comparisons keep the earliest index on ties.
+                if self.min.as_ref().is_none_or(|(_, min)| value < *min) {
+                    self.min = Some((index, value.clone()));
+                }
+                if self.max.as_ref().is_none_or(|(_, max)| value > *max) {
+                    self.max = Some((index, value));
+                }
+            }
+
+            // Combines two accumulators where `other` saw the values that
+            // directly follow the ones `self` saw. Indices from `other` are
+            // shifted accordingly, and `self`'s policy is kept.
+            pub fn merge(mut self, other: MinMaxOf<T>) -> MinMaxOf<T> {
+                let offset = self.count;
+                self.count += other.count;
+
+                // Feed the earlier of each pair first so ties still favour it.
+                for (index, value) in [other.nan, other.min, other.max].into_iter().flatten() {
+                    self.observe(index + offset, value);
+                }
+                self
+            }
+        }
+
+        impl<T: PartialOrd + Clone> Extend<T> for MinMaxOf<T> {
+            fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+                for value in iter {
+                    self.push(value);
+                }
+            }
+        }
+
+        impl<T: PartialOrd + Clone> FromIterator<T> for MinMaxOf<T> {
+            fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> MinMaxOf<T> {
+                let mut acc = MinMaxOf::new();
+                acc.extend(iter);
+                acc
+            }
+        }
+
+        impl From<MinMaxOf<i64>> for Option<MinMax> {
+            fn from(acc: MinMaxOf<i64>) -> Option<MinMax> {
+                match (acc.min, acc.max) {
+                    (Some((_, min)), Some((_, max))) => Some(MinMax(min, max)),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    // A set of `i64` values stored as sorted, disjoint `MinMax` ranges.
+    // Ranges that overlap or touch (`(1, 3)` and `(4, 6)`) are always merged,
+    // so every set has exactly one representation.
+    #[allow(dead_code)]
+    pub mod interval_set {
+        use core::fmt;
+
+        use super::MinMax;
+
+        #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+        pub struct IntervalSet {
+            ranges: Vec<MinMax>,
+        }
+
+        impl IntervalSet {
+            pub fn new() -> IntervalSet {
+                IntervalSet { ranges: Vec::new() }
+            }
+
+            // Number of disjoint ranges, not the number of values.
+            pub fn len(&self) -> usize {
+                self.ranges.len()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.ranges.is_empty()
+            }
+
+            pub fn insert(&mut self, range: MinMax) {
+                // `saturating_add` keeps ranges ending at `i64::MAX` from wrapping.
+                let start = self
+                    .ranges
+                    .partition_point(|r| r.1.saturating_add(1) < range.0);
+                let end = self
+                    .ranges
+                    .partition_point(|r| r.0 <= range.1.saturating_add(1));
+
+                let mut merged = range;
+                if start < end {
+                    merged = merged.hull(&self.ranges[start]).hull(&self.ranges[end - 1]);
+                }
+                self.ranges.splice(start..end, [merged]);
+            }
+
+            pub fn remove(&mut self, range: MinMax) {
+                let start = self.ranges.partition_point(|r| r.1 < range.0);
+                let end = self.ranges.partition_point(|r| r.0 <= range.1);
+                if start == end {
+                    return;
+                }
+
+                // Keep whatever sticks out on either side of the removed range.
+                let mut pieces = Vec::with_capacity(2);
+                let first = self.ranges[start];
+                if first.0 < range.0 {
+                    pieces.push(MinMax(first.0, range.0 - 1));
+                }
+                let last = self.ranges[end - 1];
+                if last.1 > range.1 {
+                    pieces.push(MinMax(range.1 + 1, last.1));
+                }
+                self.ranges.splice(start..end, pieces);
+            }
+
+            // Binary search over the sorted ranges: O(log n).
+            pub fn contains(&self, value: i64) -> bool {
+                let idx = self.ranges.partition_point(|r| r.1 < value);
+                self.ranges.get(idx).is_some_and(|r| r.contains(value))
+            }
+
+            pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+                let mut result = self.clone();
+                result.extend(other.ranges.iter().copied());
+                result
+            }
+
+            pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+                let mut ranges = Vec::new();
+                let (mut i, mut j) = (0, 0);
+
+                while i < self.ranges.len() && j < other.ranges.len() {
+                    let (a, b) = (self.ranges[i], other.ranges[j]);
+                    if let Some(common) = a.intersection(&b) {
+                        ranges.push(common);
+                    }
+                    // Advance whichever range finishes first.
+                    if a.1 < b.1 {
+                        i += 1;
+                    } else {
+                        j += 1;
+                    }
+                }
+
+                IntervalSet { ranges }
+            }
+
+            pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+                let mut result = self.clone();
+                for range in &other.ranges {
+                    result.remove(*range);
+                }
+                result
+            }
+
+            pub fn ranges(&self) -> impl Iterator<Item = MinMax> + '_ {
+                self.ranges.iter().copied()
+            }
+
+            // The holes between consecutive ranges, lowest first.
+            pub fn gaps(&self) -> impl Iterator<Item = MinMax> + '_ {
+                self.ranges
+                    .windows(2)
+                    .map(|pair| MinMax(pair[0].1 + 1, pair[1].0 - 1))
+            }
+        }
+
+        impl Extend<MinMax> for IntervalSet {
+            fn extend<I: IntoIterator<Item = MinMax>>(&mut self, iter: I) {
+                for range in iter {
+                    self.insert(range);
+                }
+            }
+        }
+
+        impl FromIterator<MinMax> for IntervalSet {
+            fn from_iter<I: IntoIterator<Item = MinMax>>(iter: I) -> IntervalSet {
+                let mut set = IntervalSet::new();
+                set.extend(iter);
+                set
+            }
+        }
+
+        // Displays as `{(0, 3), (10, 14)}`, reusing the `MinMax` format.
+        impl fmt::Display for IntervalSet {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{{")?;
+                for (count, range) in self.ranges.iter().enumerate() {
+                    if count != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", range)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+
+    // Tables: rows of typed cells rendered as ASCII, box drawing, Markdown or
+    // CSV. Widths are counted in terminal cells rather than bytes or chars, so
+    // "Zürich" is six wide and "東京" is four.
+    #[allow(dead_code)]
+    pub mod table {
+        use core::fmt;
+
+        use super::{City, MinMax, OwnedCity, Point2D};
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum Cell {
+            Text(String),
+            Integer(i64),
+            Float(f64),
+            Empty,
+        }
+
+        impl From<&str> for Cell {
+            fn from(text: &str) -> Self {
+                Cell::Text(text.to_owned())
+            }
+        }
+
+        impl From<String> for Cell {
+            fn from(text: String) -> Self {
+                Cell::Text(text)
+            }
+        }
+
+        impl From<i64> for Cell {
+            fn from(value: i64) -> Self {
+                Cell::Integer(value)
+            }
+        }
+
+        impl From<i32> for Cell {
+            fn from(value: i32) -> Self {
+                Cell::Integer(value.into())
+            }
+        }
+
+        impl From<f64> for Cell {
+            fn from(value: f64) -> Self {
+                Cell::Float(value)
+            }
+        }
+
+        impl From<f32> for Cell {
+            fn from(value: f32) -> Self {
+                Cell::Float(value.into())
+            }
+        }
+
+        impl<T: Into<Cell>> From<Option<T>> for Cell {
+            fn from(value: Option<T>) -> Self {
+                value.map_or(Cell::Empty, Into::into)
+            }
+        }
+
+        impl Cell {
+            fn is_number(&self) -> bool {
+                matches!(self, Cell::Integer(_) | Cell::Float(_))
+            }
+
+            fn render(&self, precision: Option<usize>) -> String {
+                match (self, precision) {
+                    (Cell::Text(text), _) => text.clone(),
+                    (Cell::Integer(value), _) => value.to_string(),
+                    (Cell::Float(value), Some(p)) => format!("{:.*}", p, value),
+                    (Cell::Float(value), None) => value.to_string(),
+                    (Cell::Empty, _) => String::new(),
+                }
+            }
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Align {
+            Left,
+            Right,
+            // Like `{:^}`, an odd leftover space goes on the right.
+            Centre,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct Column {
+            header: String,
+            align: Option<Align>,
+            max_width: Option<usize>,
+            precision: Option<usize>,
+            fill: char,
+        }
+
+        impl Column {
+            // Text is left aligned and numbers right aligned unless `align`
+            // says otherwise.
+            pub fn new(header: &str) -> Column {
+                Column {
+                    header: header.to_owned(),
+                    align: None,
+                    max_width: None,
+                    precision: None,
+                    fill: ' ',
+                }
+            }
+
+            pub fn align(mut self, align: Align) -> Column {
+                self.align = Some(align);
+                self
+            }
+
+            // Longer values are cut short and end with an ellipsis.
+            pub fn max_width(mut self, width: usize) -> Column {
+                self.max_width = Some(width);
+                self
+            }
+
+            // Decimal places for `Cell::Float`, as in `{:.3}`.
+            pub fn precision(mut self, precision: usize) -> Column {
+                self.precision = Some(precision);
+                self
+            }
+
+            // Pads the data cells, as in `{:0>5}`. Headers are always padded
+            // with spaces.
+            pub fn fill(mut self, fill: char) -> Column {
+                self.fill = fill;
+                self
+            }
+
+            pub fn header(&self) -> &str {
+                &self.header
+            }
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Style {
+            Ascii,
+            Unicode,
+            Markdown,
+            Csv,
+        }
+
+        // A row with the wrong number of cells.
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct RowLengthError {
+            pub expected: usize,
+            pub found: usize,
+        }
+
+        impl fmt::Display for RowLengthError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "row has {} cells but the table has {} columns",
+                    self.found, self.expected
+                )
+            }
+        }
+
+        impl std::error::Error for RowLengthError {}
+
+        // Types that know how to lay themselves out as a table row.
+        pub trait Row {
+            fn columns() -> Vec<Column>;
+            fn cells(&self) -> Vec<Cell>;
+        }
+
+        impl Row for City {
+            fn columns() -> Vec<Column> {
+                vec![
+                    Column::new("City"),
+                    Column::new("Latitude").precision(3),
+                    Column::new("Longitude").precision(3),
+                ]
+            }
+
+            fn cells(&self) -> Vec<Cell> {
+                vec![self.name.into(), self.lat.into(), self.lon.into()]
+            }
+        }
+
+        impl Row for OwnedCity {
+            fn columns() -> Vec<Column> {
+                City::columns()
+            }
+
+            fn cells(&self) -> Vec<Cell> {
+                vec![self.name.clone().into(), self.lat.into(), self.lon.into()]
+            }
+        }
+
+        impl Row for MinMax {
+            fn columns() -> Vec<Column> {
+                vec![Column::new("Min"), Column::new("Max")]
+            }
+
+            fn cells(&self) -> Vec<Cell> {
+                vec![self.min().into(), self.max().into()]
+            }
+        }
+
+        impl Row for Point2D {
+            fn columns() -> Vec<Column> {
+                vec![Column::new("x"), Column::new("y")]
+            }
+
+            fn cells(&self) -> Vec<Cell> {
+                vec![self.x().into(), self.y().into()]
+            }
+        }
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Table {
+            columns: Vec<Column>,
+            rows: Vec<Vec<Cell>>,
+        }
+
+        impl Table {
+            pub fn new(columns: Vec<Column>) -> Table {
+                Table {
+                    columns,
+                    rows: Vec::new(),
+                }
+            }
+
+            pub fn from_rows<'a, R: Row + 'a>(rows: impl IntoIterator<Item = &'a R>) -> Table {
+                Table {
+                    columns: R::columns(),
+                    rows: rows.into_iter().map(Row::cells).collect(),
+                }
+            }
+
+            pub fn columns(&self) -> &[Column] {
+                &self.columns
+            }
+
+            // Lets a caller tweak the columns a `Row` impl picked.
+            pub fn column_mut(&mut self, index: usize) -> Option<&mut Column> {
+                self.columns.get_mut(index)
+            }
+
+            pub fn len(&self) -> usize {
+                self.rows.len()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.rows.is_empty()
+            }
+
+            pub fn push(&mut self, row: Vec<Cell>) -> Result<(), RowLengthError> {
+                if row.len() != self.columns.len() {
+                    return Err(RowLengthError {
+                        expected: self.columns.len(),
+                        found: row.len(),
+                    });
+                }
+                self.rows.push(row);
+                Ok(())
+            }
+
+            pub fn render(&self, style: Style) -> String {
+                match style {
+                    Style::Csv => self.render_csv(),
+                    _ => self.render_grid(style),
+                }
+            }
+
+            fn render_csv(&self) -> String {
+                let mut out = String::new();
+                let headers = self.columns.iter().map(|c| c.header.clone());
+                push_csv_line(&mut out, headers);
+                for row in &self.rows {
+                    let cells = row.iter().zip(&self.columns);
+                    push_csv_line(&mut out, cells.map(|(cell, c)| cell.render(c.precision)));
+                }
+                out
+            }
+
+            fn render_grid(&self, style: Style) -> String {
+                let ellipsis = if style == Style::Ascii { "..." } else { "…" };
+                let escape = |text: String| {
+                    // Line breaks would tear the grid apart, and in Markdown
+                    // a bare `|` starts a new column.
+                    let text = text.replace(['\n', '\r'], " ");
+                    if style == Style::Markdown {
+                        text.replace('|', "\\|")
+                    } else {
+                        text
+                    }
+                };
+
+                let headers: Vec<String> = self
+                    .columns
+                    .iter()
+                    .map(|c| truncate(&escape(c.header.clone()), c.max_width, ellipsis))
+                    .collect();
+                let rows: Vec<Vec<String>> = self
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .zip(&self.columns)
+                            .map(|(cell, c)| {
+                                truncate(&escape(cell.render(c.precision)), c.max_width, ellipsis)
+                            })
+                            .collect()
+                    })
+                    .collect();
+
+                let mut widths: Vec<usize> = headers.iter().map(|h| display_width(h)).collect();
+                for row in &rows {
+                    for (width, text) in widths.iter_mut().zip(row) {
+                        *width = (*width).max(display_width(text));
+                    }
+                }
+                if style == Style::Markdown {
+                    // The delimiter row needs at least three dashes.
+                    for width in &mut widths {
+                        *width = (*width).max(3);
+                    }
+                }
+
+                // The alignment of a column follows its first non-empty cell
+                // unless it was set explicitly.
+                let aligns: Vec<Align> = (0..self.columns.len())
+                    .map(|i| {
+                        self.columns[i].align.unwrap_or_else(|| {
+                            let first = self
+                                .rows
+                                .iter()
+                                .map(|row| &row[i])
+                                .find(|c| **c != Cell::Empty);
+                            match first {
+                                Some(cell) if cell.is_number() => Align::Right,
+                                _ => Align::Left,
+                            }
+                        })
+                    })
+                    .collect();
+
+                let line = |cells: &[String], fills: &mut dyn Iterator<Item = char>| {
+                    let (left, middle, right) = match style {
+                        Style::Unicode => ("│ ", " │ ", " │"),
+                        _ => ("| ", " | ", " |"),
+                    };
+                    let padded: Vec<String> = cells
+                        .iter()
+                        .zip(&widths)
+                        .zip(&aligns)
+                        .map(|((text, &width), &align)| {
+                            pad(text, width, align, fills.next().unwrap_or(' '))
+                        })
+                        .collect();
+                    format!("{}{}{}\n", left, padded.join(middle), right)
+                };
+                let rule = |left: &str, middle: &str, right: &str, dash: &str| {
+                    let dashes: Vec<String> = widths.iter().map(|w| dash.repeat(w + 2)).collect();
+                    format!("{}{}{}\n", left, dashes.join(middle), right)
+                };
+
+                let mut out = String::new();
+                let data_fills = || self.columns.iter().map(|c| c.fill);
+                match style {
+                    Style::Ascii => {
+                        let border = rule("+", "+", "+", "-");
+                        out.push_str(&border);
+                        out.push_str(&line(&headers, &mut std::iter::repeat(' ')));
+                        out.push_str(&border);
+                        for row in &rows {
+                            out.push_str(&line(row, &mut data_fills()));
+                        }
+                        out.push_str(&border);
+                    }
+                    Style::Unicode => {
+                        out.push_str(&rule("┌", "┬", "┐", "─"));
+                        out.push_str(&line(&headers, &mut std::iter::repeat(' ')));
+                        out.push_str(&rule("├", "┼", "┤", "─"));
+                        for row in &rows {
+                            out.push_str(&line(row, &mut data_fills()));
+                        }
+                        out.push_str(&rule("└", "┴", "┘", "─"));
+                    }
+                    Style::Markdown => {
+                        out.push_str(&line(&headers, &mut std::iter::repeat(' ')));
+                        let delimiters: Vec<String> = widths
+                            .iter()
+                            .zip(&aligns)
+                            .map(|(&width, align)| match align {
+                                Align::Left => format!(":{}", "-".repeat(width - 1)),
+                                Align::Right => format!("{}:", "-".repeat(width - 1)),
+                                Align::Centre => format!(":{}:", "-".repeat(width - 2)),
+                            })
+                            .collect();
+                        out.push_str(&format!("| {} |\n", delimiters.join(" | ")));
+                        for row in &rows {
+                            out.push_str(&line(row, &mut data_fills()));
+                        }
+                    }
+                    Style::Csv => unreachable!("handled by render_csv"),
+                }
+                out
+            }
+        }
+
+        // Defaults to the ASCII style.
+        impl fmt::Display for Table {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let style = if f.alternate() {
+                    Style::Unicode
+                } else {
+                    Style::Ascii
+                };
+                write!(f, "{}", self.render(style))
+            }
+        }
+
+        // RFC 4180: fields holding a comma, quote or line break are quoted,
+        // and quotes inside them are doubled.
+        fn push_csv_line(out: &mut String, fields: impl Iterator<Item = String>) {
+            let fields: Vec<String> = fields
+                .map(|field| {
+                    if field.contains([',', '"', '\n', '\r']) {
+                        format!("\"{}\"", field.replace('"', "\"\""))
+                    } else {
+                        field
+                    }
+                })
+                .collect();
+            out.push_str(&fields.join(","));
+            out.push_str("\r\n");
+        }
+
+        fn pad(text: &str, width: usize, align: Align, fill: char) -> String {
+            let gap = width.saturating_sub(display_width(text));
+            let (before, after) = match align {
+                Align::Left => (0, gap),
+                Align::Right => (gap, 0),
+                Align::Centre => (gap / 2, gap - gap / 2),
+            };
+            let fill = fill.to_string();
+            format!("{}{}{}", fill.repeat(before), text, fill.repeat(after))
+        }
+
+        // Cuts `text` down to `max` cells, ellipsis included. A wide character
+        // that would straddle the limit is dropped whole.
+        fn truncate(text: &str, max: Option<usize>, ellipsis: &str) -> String {
+            let max = match max {
+                Some(max) if display_width(text) > max => max,
+                _ => return text.to_owned(),
+            };
+            let ellipsis_width = display_width(ellipsis);
+            if max < ellipsis_width {
+                return ellipsis.chars().take(max).collect();
+            }
+
+            let mut out = String::new();
+            let mut used = 0;
+            for c in text.chars() {
+                let width = char_width(c);
+                if used + width > max - ellipsis_width {
+                    break;
+                }
+                used += width;
+                out.push(c);
+            }
+            out.push_str(ellipsis);
+            out
+        }
+
+        // Terminal cells taken up by `text`.
+        pub fn display_width(text: &str) -> usize {
+            text.chars().map(char_width).sum()
+        }
+
+        // A rough take on `wcwidth`: control characters and combining marks
+        // take no room, East Asian wide characters and emoji take two.
+        fn char_width(c: char) -> usize {
+            const ZERO: &[(u32, u32)] = &[
+                (0x0300, 0x036f),
+                (0x0483, 0x0489),
+                (0x0591, 0x05bd),
+                (0x0610, 0x061a),
+                (0x064b, 0x065f),
+                (0x0e31, 0x0e31),
+                (0x0e34, 0x0e3a),
+                (0x0e47, 0x0e4e),
+                (0x1ab0, 0x1aff),
+                (0x1dc0, 0x1dff),
+                (0x200b, 0x200f),
+                (0x20d0, 0x20ff),
+                (0x302a, 0x302f),
+                (0x3099, 0x309a),
+                (0xfe00, 0xfe0f),
+                (0xfe20, 0xfe2f),
+                (0xfeff, 0xfeff),
+            ];
+            const WIDE: &[(u32, u32)] = &[
+                (0x1100, 0x115f),
+                (0x2e80, 0x303e),
+                (0x3041, 0x33ff),
+                (0x3400, 0x4dbf),
+                (0x4e00, 0x9fff),
+                (0xa000, 0xa4cf),
+                (0xac00, 0xd7a3),
+                (0xf900, 0xfaff),
+                (0xfe30, 0xfe4f),
+                (0xff00, 0xff60),
+                (0xffe0, 0xffe6),
+                (0x1f300, 0x1f64f),
+                (0x1f900, 0x1f9ff),
+                (0x20000, 0x3fffd),
+            ];
+
+            let code = u32::from(c);
+            let within =
+                |ranges: &[(u32, u32)]| ranges.iter().any(|&(lo, hi)| (lo..=hi).contains(&code));
+            if c.is_control() || within(ZERO) {
+                0
+            } else if within(WIDE) {
+                2
+            } else {
+                1
+            }
+        }
+    }
+
+    // The RBE `Color` exercise: an sRGB colour with hex output, conversions
+    // to the cylindrical HSL and HSV models, CSS names and terminal escapes.
+    #[allow(dead_code)]
+    pub mod color {
+        use core::fmt;
+        use std::str::FromStr;
+
+        use super::parse::{Cursor, Expected, ParseError};
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub struct Rgb {
+            pub red: u8,
+            pub green: u8,
+            pub blue: u8,
+        }
+
+        // `hue` is in degrees, `[0, 360)`; the others are fractions in
+        // `[0, 1]`.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct Hsl {
+            pub hue: f64,
+            pub saturation: f64,
+            pub lightness: f64,
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct Hsv {
+            pub hue: f64,
+            pub saturation: f64,
+            pub value: f64,
+        }
+
+        // How many colours the terminal can show.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum ColorDepth {
+            TrueColor,
+            Ansi256,
+        }
+
+        impl ColorDepth {
+            // Terminals that do 24-bit colour advertise it through
+            // `COLORTERM`; anything else gets the 256-colour palette.
+            pub fn detect() -> ColorDepth {
+                match std::env::var("COLORTERM").as_deref() {
+                    Ok("truecolor") | Ok("24bit") => ColorDepth::TrueColor,
+                    _ => ColorDepth::Ansi256,
+                }
+            }
+        }
+
+        impl Rgb {
+            pub const fn new(red: u8, green: u8, blue: u8) -> Rgb {
+                Rgb { red, green, blue }
+            }
+
+            // Case-insensitive lookup in the CSS named colours.
+            pub fn named(name: &str) -> Option<Rgb> {
+                CSS_NAMES
+                    .iter()
+                    .find(|(css, _)| css.eq_ignore_ascii_case(name))
+                    .map(|&(_, rgb)| rgb)
+            }
+
+            // The CSS name for this exact colour, if it has one. Where names
+            // share a colour the first alphabetically wins, so `aqua` over
+            // `cyan` and `gray` over `grey`.
+            pub fn name(&self) -> Option<&'static str> {
+                CSS_NAMES
+                    .iter()
+                    .find(|(_, rgb)| rgb == self)
+                    .map(|&(css, _)| css)
+            }
+
+            // Mixes channel by channel: `t = 0` gives `self`, `t = 1` gives
+            // `other`. `t` is clamped to that range.
+            pub fn blend(&self, other: Rgb, t: f64) -> Rgb {
+                let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+                let mix =
+                    |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+                Rgb::new(
+                    mix(self.red, other.red),
+                    mix(self.green, other.green),
+                    mix(self.blue, other.blue),
+                )
+            }
+
+            // WCAG 2 relative luminance: 0 for black, 1 for white.
+            pub fn luminance(&self) -> f64 {
+                let linear = |channel: u8| {
+                    let c = f64::from(channel) / 255.0;
+                    if c <= 0.04045 {
+                        c / 12.92
+                    } else {
+                        ((c + 0.055) / 1.055).powf(2.4)
+                    }
+                };
+                0.2126 * linear(self.red) + 0.7152 * linear(self.green) + 0.0722 * linear(self.blue)
+            }
+
+            // WCAG contrast ratio, from 1 (identical) to 21 (black on white).
+            // Order doesn't matter.
+            pub fn contrast_ratio(&self, other: Rgb) -> f64 {
+                let (a, b) = (self.luminance(), other.luminance());
+                (a.max(b) + 0.05) / (a.min(b) + 0.05)
+            }
+
+            // Nearest entry in the xterm 256-colour palette, looking at the
+            // 6×6×6 cube and the grey ramp. The 16 system colours are left
+            // out because terminals are free to redefine them.
+            pub fn to_ansi256(self) -> u8 {
+                const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+                let level = |v: u8| match v {
+                    0..=47 => 0,
+                    48..=114 => 1,
+                    _ => (v - 35) / 40,
+                };
+                let (r, g, b) = (level(self.red), level(self.green), level(self.blue));
+                let cube = Rgb::new(LEVELS[r as usize], LEVELS[g as usize], LEVELS[b as usize]);
+
+                let average =
+                    (u16::from(self.red) + u16::from(self.green) + u16::from(self.blue)) / 3;
+                let step = (average.saturating_sub(3) / 10).min(23) as u8;
+                let grey = 8 + 10 * step;
+
+                if self.distance(Rgb::new(grey, grey, grey)) < self.distance(cube) {
+                    232 + step
+                } else {
+                    16 + 36 * r + 6 * g + b
+                }
+            }
+
+            // Escape sequence that sets this as the foreground colour.
+            pub fn ansi_fg(&self, depth: ColorDepth) -> String {
+                match depth {
+                    ColorDepth::TrueColor => {
+                        format!("\x1b[38;2;{};{};{}m", self.red, self.green, self.blue)
+                    }
+                    ColorDepth::Ansi256 => format!("\x1b[38;5;{}m", self.to_ansi256()),
+                }
+            }
+
+            pub fn ansi_bg(&self, depth: ColorDepth) -> String {
+                match depth {
+                    ColorDepth::TrueColor => {
+                        format!("\x1b[48;2;{};{};{}m", self.red, self.green, self.blue)
+                    }
+                    ColorDepth::Ansi256 => format!("\x1b[48;5;{}m", self.to_ansi256()),
+                }
+            }
+
+            // `text` in this colour, followed by a reset.
+            pub fn paint(&self, text: &str, depth: ColorDepth) -> String {
+                format!("{}{}\x1b[0m", self.ansi_fg(depth), text)
+            }
+
+            // Squared distance in RGB space; good enough to pick a palette
+            // entry.
+            fn distance(&self, other: Rgb) -> u32 {
+                let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).unsigned_abs().pow(2);
+                d(self.red, other.red) + d(self.green, other.green) + d(self.blue, other.blue)
+            }
+
+            // Hue in degrees plus the largest and smallest channel, shared by
+            // the HSL and HSV conversions.
+            fn hue_and_range(&self) -> (f64, f64, f64) {
+                let (r, g, b) = (
+                    f64::from(self.red) / 255.0,
+                    f64::from(self.green) / 255.0,
+                    f64::from(self.blue) / 255.0,
+                );
+                let max = r.max(g).max(b);
+                let min = r.min(g).min(b);
+                let delta = max - min;
+
+                let hue = if delta == 0.0 {
+                    0.0
+                } else if max == r {
+                    60.0 * ((g - b) / delta).rem_euclid(6.0)
+                } else if max == g {
+                    60.0 * ((b - r) / delta + 2.0)
+                } else {
+                    60.0 * ((r - g) / delta + 4.0)
+                };
+                (hue, max, min)
+            }
+
+            // Builds a colour from a hue and the chroma and offset that HSL
+            // and HSV each work out in their own way.
+            fn from_hue(hue: f64, chroma: f64, offset: f64) -> Rgb {
+                let hue = hue.rem_euclid(360.0) / 60.0;
+                let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+                let (r, g, b) = match hue as u8 {
+                    0 => (chroma, x, 0.0),
+                    1 => (x, chroma, 0.0),
+                    2 => (0.0, chroma, x),
+                    3 => (0.0, x, chroma),
+                    4 => (x, 0.0, chroma),
+                    _ => (chroma, 0.0, x),
+                };
+                let channel = |v: f64| ((v + offset) * 255.0).round().clamp(0.0, 255.0) as u8;
+                Rgb::new(channel(r), channel(g), channel(b))
+            }
+        }
+
+        impl From<Rgb> for Hsl {
+            fn from(rgb: Rgb) -> Hsl {
+                let (hue, max, min) = rgb.hue_and_range();
+                let lightness = (max + min) / 2.0;
+                let saturation = if max == min {
+                    0.0
+                } else {
+                    (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+                };
+                Hsl {
+                    hue,
+                    saturation,
+                    lightness,
+                }
+            }
+        }
+
+        // Out of range saturation and lightness are clamped; hue wraps.
+        impl From<Hsl> for Rgb {
+            fn from(hsl: Hsl) -> Rgb {
+                let saturation = hsl.saturation.clamp(0.0, 1.0);
+                let lightness = hsl.lightness.clamp(0.0, 1.0);
+                let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+                Rgb::from_hue(hsl.hue, chroma, lightness - chroma / 2.0)
+            }
+        }
+
+        impl From<Rgb> for Hsv {
+            fn from(rgb: Rgb) -> Hsv {
+                let (hue, max, min) = rgb.hue_and_range();
+                let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+                Hsv {
+                    hue,
+                    saturation,
+                    value: max,
+                }
+            }
+        }
+
+        impl From<Hsv> for Rgb {
+            fn from(hsv: Hsv) -> Rgb {
+                let saturation = hsv.saturation.clamp(0.0, 1.0);
+                let value = hsv.value.clamp(0.0, 1.0);
+                let chroma = value * saturation;
+                Rgb::from_hue(hsv.hue, chroma, value - chroma)
+            }
+        }
+
+        // "RGB (128, 255, 90) 0x80FF5A", as in the RBE exercise.
+        impl fmt::Display for Rgb {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let text = format!(
+                    "RGB ({}, {}, {}) 0x{:X}",
+                    self.red, self.green, self.blue, self
+                );
+                super::pad(f, &text)
+            }
+        }
+
+        // Always six digits; `{:#X}` adds the `0x`.
+        impl fmt::UpperHex for Rgb {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let prefix = if f.alternate() { "0x" } else { "" };
+                write!(
+                    f,
+                    "{}{:02X}{:02X}{:02X}",
+                    prefix, self.red, self.green, self.blue
+                )
+            }
+        }
+
+        impl fmt::LowerHex for Rgb {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let prefix = if f.alternate() { "0x" } else { "" };
+                write!(
+                    f,
+                    "{}{:02x}{:02x}{:02x}",
+                    prefix, self.red, self.green, self.blue
+                )
+            }
+        }
+
+        // "hsl(96, 100%, 68%)"; precision applies to every component.
+        impl fmt::Display for Hsl {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let p = f.precision().unwrap_or(0);
+                write!(
+                    f,
+                    "hsl({:.*}, {:.*}%, {:.*}%)",
+                    p,
+                    self.hue,
+                    p,
+                    self.saturation * 100.0,
+                    p,
+                    self.lightness * 100.0
+                )
+            }
+        }
+
+        impl fmt::Display for Hsv {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let p = f.precision().unwrap_or(0);
+                write!(
+                    f,
+                    "hsv({:.*}, {:.*}%, {:.*}%)",
+                    p,
+                    self.hue,
+                    p,
+                    self.saturation * 100.0,
+                    p,
+                    self.value * 100.0
+                )
+            }
+        }
+
+        // "#80ff5a", "#8f5", "rgb(128, 255, 90)" or a CSS name. Hex digits
+        // may be either case.
+        impl FromStr for Rgb {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Rgb, ParseError> {
+                let mut cursor = Cursor::new(s);
+                let rgb = if cursor.eat("#") {
+                    let start = cursor.pos;
+                    let digits = cursor
+                        .rest()
+                        .chars()
+                        .take_while(char::is_ascii_hexdigit)
+                        .count();
+                    let hex = |at: usize, len: usize| {
+                        u8::from_str_radix(&s[start + at..start + at + len], 16)
+                            .expect("hex digits")
+                    };
+                    let rgb = match digits {
+                        6 => Rgb::new(hex(0, 2), hex(2, 2), hex(4, 2)),
+                        // Each digit is doubled: `#8f5` is `#88ff55`.
+                        3 => Rgb::new(hex(0, 1) * 17, hex(1, 1) * 17, hex(2, 1) * 17),
+                        _ => return Err(cursor.error(Expected::Value("3 or 6 hex digits"))),
+                    };
+                    cursor.pos += digits;
+                    rgb
+                } else if cursor.eat("rgb(") {
+                    let mut channels = [0; 3];
+                    for (index, channel) in channels.iter_mut().enumerate() {
+                        if index != 0 {
+                            cursor.skip_whitespace();
+                            cursor.text(",")?;
+                        }
+                        cursor.skip_whitespace();
+                        *channel = cursor.token(
+                            |c| c.is_ascii_digit(),
+                            Expected::Value("a channel from 0 to 255"),
+                        )?;
+                    }
+                    cursor.skip_whitespace();
+                    cursor.text(")")?;
+                    Rgb::new(channels[0], channels[1], channels[2])
+                } else {
+                    let name = cursor.rest();
+                    let rgb = Rgb::named(name).ok_or_else(|| {
+                        cursor.error(Expected::OneOf(&["#", "rgb(", "a CSS colour name"]))
+                    })?;
+                    cursor.pos += name.len();
+                    rgb
+                };
+                cursor.end()?;
+                Ok(rgb)
+            }
+        }
+
+        const CSS_NAMES: &[(&str, Rgb)] = &[
+            ("aliceblue", Rgb::new(240, 248, 255)),
+            ("antiquewhite", Rgb::new(250, 235, 215)),
+            ("aqua", Rgb::new(0, 255, 255)),
+            ("aquamarine", Rgb::new(127, 255, 212)),
+            ("azure", Rgb::new(240, 255, 255)),
+            ("beige", Rgb::new(245, 245, 220)),
+            ("bisque", Rgb::new(255, 228, 196)),
+            ("black", Rgb::new(0, 0, 0)),
+            ("blanchedalmond", Rgb::new(255, 235, 205)),
+            ("blue", Rgb::new(0, 0, 255)),
+            ("blueviolet", Rgb::new(138, 43, 226)),
+            ("brown", Rgb::new(165, 42, 42)),
+            ("burlywood", Rgb::new(222, 184, 135)),
+            ("cadetblue", Rgb::new(95, 158, 160)),
+            ("chartreuse", Rgb::new(127, 255, 0)),
+            ("chocolate", Rgb::new(210, 105, 30)),
+            ("coral", Rgb::new(255, 127, 80)),
+            ("cornflowerblue", Rgb::new(100, 149, 237)),
+            ("cornsilk", Rgb::new(255, 248, 220)),
+            ("crimson", Rgb::new(220, 20, 60)),
+            ("cyan", Rgb::new(0, 255, 255)),
+            ("darkblue", Rgb::new(0, 0, 139)),
+            ("darkcyan", Rgb::new(0, 139, 139)),
+            ("darkgoldenrod", Rgb::new(184, 134, 11)),
+            ("darkgray", Rgb::new(169, 169, 169)),
+            ("darkgreen", Rgb::new(0, 100, 0)),
+            ("darkgrey", Rgb::new(169, 169, 169)),
+            ("darkkhaki", Rgb::new(189, 183, 107)),
+            ("darkmagenta", Rgb::new(139, 0, 139)),
+            ("darkolivegreen", Rgb::new(85, 107, 47)),
+            ("darkorange", Rgb::new(255, 140, 0)),
+            ("darkorchid", Rgb::new(153, 50, 204)),
+            ("darkred", Rgb::new(139, 0, 0)),
+            ("darksalmon", Rgb::new(233, 150, 122)),
+            ("darkseagreen", Rgb::new(143, 188, 143)),
+            ("darkslateblue", Rgb::new(72, 61, 139)),
+            ("darkslategray", Rgb::new(47, 79, 79)),
+            ("darkslategrey", Rgb::new(47, 79, 79)),
+            ("darkturquoise", Rgb::new(0, 206, 209)),
+            ("darkviolet", Rgb::new(148, 0, 211)),
+            ("deeppink", Rgb::new(255, 20, 147)),
+            ("deepskyblue", Rgb::new(0, 191, 255)),
+            ("dimgray", Rgb::new(105, 105, 105)),
+            ("dimgrey", Rgb::new(105, 105, 105)),
+            ("dodgerblue", Rgb::new(30, 144, 255)),
+            ("firebrick", Rgb::new(178, 34, 34)),
+            ("floralwhite", Rgb::new(255, 250, 240)),
+            ("forestgreen", Rgb::new(34, 139, 34)),
+            ("fuchsia", Rgb::new(255, 0, 255)),
+            ("gainsboro", Rgb::new(220, 220, 220)),
+            ("ghostwhite", Rgb::new(248, 248, 255)),
+            ("gold", Rgb::new(255, 215, 0)),
+            ("goldenrod", Rgb::new(218, 165, 32)),
+            ("gray", Rgb::new(128, 128, 128)),
+            ("green", Rgb::new(0, 128, 0)),
+            ("greenyellow", Rgb::new(173, 255, 47)),
+            ("grey", Rgb::new(128, 128, 128)),
+            ("honeydew", Rgb::new(240, 255, 240)),
+            ("hotpink", Rgb::new(255, 105, 180)),
+            ("indianred", Rgb::new(205, 92, 92)),
+            ("indigo", Rgb::new(75, 0, 130)),
+            ("ivory", Rgb::new(255, 255, 240)),
+            ("khaki", Rgb::new(240, 230, 140)),
+            ("lavender", Rgb::new(230, 230, 250)),
+            ("lavenderblush", Rgb::new(255, 240, 245)),
+            ("lawngreen", Rgb::new(124, 252, 0)),
+            ("lemonchiffon", Rgb::new(255, 250, 205)),
+            ("lightblue", Rgb::new(173, 216, 230)),
+            ("lightcoral", Rgb::new(240, 128, 128)),
+            ("lightcyan", Rgb::new(224, 255, 255)),
+            ("lightgoldenrodyellow", Rgb::new(250, 250, 210)),
+            ("lightgray", Rgb::new(211, 211, 211)),
+            ("lightgreen", Rgb::new(144, 238, 144)),
+            ("lightgrey", Rgb::new(211, 211, 211)),
+            ("lightpink", Rgb::new(255, 182, 193)),
+            ("lightsalmon", Rgb::new(255, 160, 122)),
+            ("lightseagreen", Rgb::new(32, 178, 170)),
+            ("lightskyblue", Rgb::new(135, 206, 250)),
+            ("lightslategray", Rgb::new(119, 136, 153)),
+            ("lightslategrey", Rgb::new(119, 136, 153)),
+            ("lightsteelblue", Rgb::new(176, 196, 222)),
+            ("lightyellow", Rgb::new(255, 255, 224)),
+            ("lime", Rgb::new(0, 255, 0)),
+            ("limegreen", Rgb::new(50, 205, 50)),
+            ("linen", Rgb::new(250, 240, 230)),
+            ("magenta", Rgb::new(255, 0, 255)),
+            ("maroon", Rgb::new(128, 0, 0)),
+            ("mediumaquamarine", Rgb::new(102, 205, 170)),
+            ("mediumblue", Rgb::new(0, 0, 205)),
+            ("mediumorchid", Rgb::new(186, 85, 211)),
+            ("mediumpurple", Rgb::new(147, 112, 219)),
+            ("mediumseagreen", Rgb::new(60, 179, 113)),
+            ("mediumslateblue", Rgb::new(123, 104, 238)),
+            ("mediumspringgreen", Rgb::new(0, 250, 154)),
+            ("mediumturquoise", Rgb::new(72, 209, 204)),
+            ("mediumvioletred", Rgb::new(199, 21, 133)),
+            ("midnightblue", Rgb::new(25, 25, 112)),
+            ("mintcream", Rgb::new(245, 255, 250)),
+            ("mistyrose", Rgb::new(255, 228, 225)),
+            ("moccasin", Rgb::new(255, 228, 181)),
+            ("navajowhite", Rgb::new(255, 222, 173)),
+            ("navy", Rgb::new(0, 0, 128)),
+            ("oldlace", Rgb::new(253, 245, 230)),
+            ("olive", Rgb::new(128, 128, 0)),
+            ("olivedrab", Rgb::new(107, 142, 35)),
+            ("orange", Rgb::new(255, 165, 0)),
+            ("orangered", Rgb::new(255, 69, 0)),
+            ("orchid", Rgb::new(218, 112, 214)),
+            ("palegoldenrod", Rgb::new(238, 232, 170)),
+            ("palegreen", Rgb::new(152, 251, 152)),
+            ("paleturquoise", Rgb::new(175, 238, 238)),
+            ("palevioletred", Rgb::new(219, 112, 147)),
+            ("papayawhip", Rgb::new(255, 239, 213)),
+            ("peachpuff", Rgb::new(255, 218, 185)),
+            ("peru", Rgb::new(205, 133, 63)),
+            ("pink", Rgb::new(255, 192, 203)),
+            ("plum", Rgb::new(221, 160, 221)),
+            ("powderblue", Rgb::new(176, 224, 230)),
+            ("purple", Rgb::new(128, 0, 128)),
+            ("rebeccapurple", Rgb::new(102, 51, 153)),
+            ("red", Rgb::new(255, 0, 0)),
+            ("rosybrown", Rgb::new(188, 143, 143)),
+            ("royalblue", Rgb::new(65, 105, 225)),
+            ("saddlebrown", Rgb::new(139, 69, 19)),
+            ("salmon", Rgb::new(250, 128, 114)),
+            ("sandybrown", Rgb::new(244, 164, 96)),
+            ("seagreen", Rgb::new(46, 139, 87)),
+            ("seashell", Rgb::new(255, 245, 238)),
+            ("sienna", Rgb::new(160, 82, 45)),
+            ("silver", Rgb::new(192, 192, 192)),
+            ("skyblue", Rgb::new(135, 206, 235)),
+            ("slateblue", Rgb::new(106, 90, 205)),
+            ("slategray", Rgb::new(112, 128, 144)),
+            ("slategrey", Rgb::new(112, 128, 144)),
+            ("snow", Rgb::new(255, 250, 250)),
+            ("springgreen", Rgb::new(0, 255, 127)),
+            ("steelblue", Rgb::new(70, 130, 180)),
+            ("tan", Rgb::new(210, 180, 140)),
+            ("teal", Rgb::new(0, 128, 128)),
+            ("thistle", Rgb::new(216, 191, 216)),
+            ("tomato", Rgb::new(255, 99, 71)),
+            ("turquoise", Rgb::new(64, 224, 208)),
+            ("violet", Rgb::new(238, 130, 238)),
+            ("wheat", Rgb::new(245, 222, 179)),
+            ("white", Rgb::new(255, 255, 255)),
+            ("whitesmoke", Rgb::new(245, 245, 245)),
+            ("yellow", Rgb::new(255, 255, 0)),
+            ("yellowgreen", Rgb::new(154, 205, 50)),
+        ];
+    }
+
+    // `Display` wrappers for numbers in reports: digit grouping, SI and
+    // binary prefixes, ordinals, Roman numerals and arbitrary radixes. Like
+    // Rust's own numbers they are right-aligned when given a width.
+    #[allow(dead_code)]
+    pub mod numbers {
+        use core::fmt;
+        use std::str::FromStr;
+
+        use super::pad_aligned;
+        use super::parse::{Expected, ParseError};
+
+        // The thousands and decimal separators of a locale.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct Locale {
+            pub group: char,
+            pub decimal: char,
+        }
+
+        impl Locale {
+            pub const EN: Locale = Locale {
+                group: ',',
+                decimal: '.',
+            };
+            pub const DE: Locale = Locale {
+                group: '.',
+                decimal: ',',
+            };
+            // French uses a narrow no-break space.
+            pub const FR: Locale = Locale {
+                group: '\u{202f}',
+                decimal: ',',
+            };
+            pub const CH: Locale = Locale {
+                group: '\'',
+                decimal: '.',
+            };
+        }
+
+        // `1234567.8` as `1,234,567.8`. Precision is passed on to the value,
+        // so `{:.2}` on an `f64` gives two decimal places.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct Grouped<T> {
+            value: T,
+            locale: Locale,
+        }
+
+        impl<T: fmt::Display> Grouped<T> {
+            pub fn new(value: T, locale: Locale) -> Grouped<T> {
+                Grouped { value, locale }
+            }
+        }
+
+        impl<T: fmt::Display> fmt::Display for Grouped<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let text = match f.precision() {
+                    Some(precision) => format!("{:.*}", precision, self.value),
+                    None => self.value.to_string(),
+                };
+                let (sign, unsigned) = match text.strip_prefix('-') {
+                    Some(rest) => ("-", rest),
+                    None => ("", text.as_str()),
+                };
+                // `inf` and `NaN` have no digits to group.
+                if !unsigned.starts_with(|c: char| c.is_ascii_digit()) {
+                    return pad_aligned(f, &text, fmt::Alignment::Right);
+                }
+
+                let (whole, fraction) = match unsigned.split_once('.') {
+                    Some((whole, fraction)) => (whole, Some(fraction)),
+                    None => (unsigned, None),
+                };
+                let mut grouped = String::from(sign);
+                grouped.push_str(&group_digits(whole, 3, self.locale.group));
+                if let Some(fraction) = fraction {
+                    grouped.push(self.locale.decimal);
+                    grouped.push_str(fraction);
+                }
+                pad_aligned(f, &grouped, fmt::Alignment::Right)
+            }
+        }
+
+        // Inserts `separator` every `size` digits, counting from the right.
+        fn group_digits(digits: &str, size: usize, separator: char) -> String {
+            let mut grouped = String::with_capacity(digits.len() * 2);
+            for (count, c) in digits.chars().enumerate() {
+                if count != 0 && (digits.len() - count).is_multiple_of(size) {
+                    grouped.push(separator);
+                }
+                grouped.push(c);
+            }
+            grouped
+        }
+
+        // `1234.0` as `1.2 k`, scaled by powers of 1000 from quecto to
+        // quetta. One decimal place unless a precision is given.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct SiPrefix {
+            value: f64,
+            unit: &'static str,
+        }
+
+        impl SiPrefix {
+            pub fn new(value: f64) -> SiPrefix {
+                SiPrefix { value, unit: "" }
+            }
+
+            // Written straight after the prefix: `1.2 km`.
+            pub fn unit(mut self, unit: &'static str) -> SiPrefix {
+                self.unit = unit;
+                self
+            }
+        }
+
+        impl fmt::Display for SiPrefix {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                // Index 10 is the unprefixed unit.
+                const PREFIXES: [&str; 21] = [
+                    "q", "r", "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P",
+                    "E", "Z", "Y", "R", "Q",
+                ];
+                let precision = f.precision().unwrap_or(1);
+                let text = scaled(self.value, 1000.0, &PREFIXES, 10, precision, self.unit);
+                pad_aligned(f, &text, fmt::Alignment::Right)
+            }
+        }
+
+        // `3565158.0` bytes as `3.4 MiB`, scaled by powers of 1024. Plain
+        // counts below 1024 are written without decimals: `512 B`.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct BinaryPrefix {
+            value: f64,
+            unit: &'static str,
+        }
+
+        impl BinaryPrefix {
+            pub fn new(value: f64) -> BinaryPrefix {
+                BinaryPrefix { value, unit: "" }
+            }
+
+            pub fn bytes(count: u64) -> BinaryPrefix {
+                BinaryPrefix::new(count as f64).unit("B")
+            }
+
+            pub fn unit(mut self, unit: &'static str) -> BinaryPrefix {
+                self.unit = unit;
+                self
+            }
+        }
+
+        impl fmt::Display for BinaryPrefix {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                const PREFIXES: [&str; 11] = [
+                    "", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi", "Ri", "Qi",
+                ];
+                let precision = f.precision().unwrap_or(1);
+                let text = scaled(self.value, 1024.0, &PREFIXES, 0, precision, self.unit);
+                pad_aligned(f, &text, fmt::Alignment::Right)
+            }
+        }
+
+        // Picks the prefix that leaves between 1 and `base` in front of it.
+        // `unit_index` is the position of the empty prefix in `prefixes`.
+        fn scaled(
+            value: f64,
+            base: f64,
+            prefixes: &[&str],
+            unit_index: usize,
+            precision: usize,
+            unit: &str,
+        ) -> String {
+            let space = if unit.is_empty() { "" } else { " " };
+            if value == 0.0 || !value.is_finite() {
+                return format!("{}{}{}", value, space, unit);
+            }
+
+            let mut index = unit_index as i32 + value.abs().log(base).floor() as i32;
+            index = index.clamp(0, prefixes.len() as i32 - 1);
+            let mut mantissa = value / base.powi(index - unit_index as i32);
+            // `999.96` rounds to `1000.0`, which belongs to the next prefix.
+            let rounded: f64 = format!("{:.*}", precision, mantissa.abs())
+                .parse()
+                .unwrap_or(0.0);
+            if rounded >= base && (index as usize) < prefixes.len() - 1 {
+                index += 1;
+                mantissa /= base;
+            }
+
+            let prefix = prefixes[index as usize];
+            let space = if prefix.is_empty() && unit.is_empty() {
+                ""
+            } else {
+                " "
+            };
+            if index as usize == unit_index && mantissa.fract() == 0.0 {
+                format!("{}{}{}{}", mantissa, space, prefix, unit)
+            } else {
+                format!("{:.*}{}{}{}", precision, mantissa, space, prefix, unit)
+            }
+        }
+
+        // `1st`, `22nd`, `113th`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct Ordinal(pub i128);
+
+        impl fmt::Display for Ordinal {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let n = self.0.unsigned_abs();
+                // Eleventh to thirteenth break the pattern.
+                let suffix = match (n % 10, n % 100) {
+                    (_, 11..=13) => "th",
+                    (1, _) => "st",
+                    (2, _) => "nd",
+                    (3, _) => "rd",
+                    _ => "th",
+                };
+                pad_aligned(f, &format!("{}{}", self.0, suffix), fmt::Alignment::Right)
+            }
+        }
+
+        // A number from 1 to 3999 in Roman numerals, `MCMXCIV`. `{:#}` writes
+        // it in lower case.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct Roman(u16);
+
+        const NUMERALS: [(u16, &str); 13] = [
+            (1000, "M"),
+            (900, "CM"),
+            (500, "D"),
+            (400, "CD"),
+            (100, "C"),
+            (90, "XC"),
+            (50, "L"),
+            (40, "XL"),
+            (10, "X"),
+            (9, "IX"),
+            (5, "V"),
+            (4, "IV"),
+            (1, "I"),
+        ];
+
+        impl Roman {
+            pub const MAX: u16 = 3999;
+
+            // `None` for zero and anything past `MMMCMXCIX`, which have no
+            // standard form.
+            pub fn new(value: u16) -> Option<Roman> {
+                (1..=Roman::MAX).contains(&value).then_some(Roman(value))
+            }
+
+            pub fn value(self) -> u16 {
+                self.0
+            }
+
+            fn encode(self) -> String {
+                let mut rest = self.0;
+                let mut text = String::new();
+                for (value, numeral) in NUMERALS {
+                    while rest >= value {
+                        text.push_str(numeral);
+                        rest -= value;
+                    }
+                }
+                text
+            }
+        }
+
+        impl fmt::Display for Roman {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut text = self.encode();
+                if f.alternate() {
+                    text.make_ascii_lowercase();
+                }
+                pad_aligned(f, &text, fmt::Alignment::Right)
+            }
+        }
+
+        // Either case, but only the standard form: `IV`, never `IIII`.
+        impl FromStr for Roman {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Roman, ParseError> {
+                const DIGITS: &[&str] = &["I", "V", "X", "L", "C", "D", "M"];
+                const VALUES: [u32; 7] = [1, 5, 10, 50, 100, 500, 1000];
+
+                let upper = s.to_ascii_uppercase();
+                let mut values = Vec::with_capacity(s.len());
+                for (offset, c) in upper.char_indices() {
+                    match DIGITS.iter().position(|d| d.starts_with(c)) {
+                        Some(index) => values.push(VALUES[index]),
+                        None => {
+                            return Err(ParseError {
+                                offset,
+                                expected: Expected::OneOf(DIGITS),
+                            })
+                        }
+                    }
+                }
+                if values.is_empty() {
+                    return Err(ParseError {
+                        offset: 0,
+                        expected: Expected::OneOf(DIGITS),
+                    });
+                }
+
+                // A digit before a bigger one is subtracted.
+                let total: u32 = (0..values.len())
+                    .map(|i| match values.get(i + 1) {
+                        Some(&next) if next > values[i] => -(values[i] as i64),
+                        _ => values[i] as i64,
+                    })
+                    .sum::<i64>()
+                    .max(0) as u32;
+
+                // Anything that isn't exactly how `total` is written, such as
+                // `IIII` or `IC`, fails where it first differs. Past the
+                // largest numeral that is where `MMMCMXCIX` stops matching.
+                let roman = Roman(total.clamp(1, u32::from(Roman::MAX)) as u16);
+                let written = roman.encode();
+                if written != upper {
+                    let offset = written
+                        .bytes()
+                        .zip(upper.bytes())
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    return Err(ParseError {
+                        offset,
+                        expected: Expected::Value(
+                            "a Roman numeral from I to MMMCMXCIX in standard form",
+                        ),
+                    });
+                }
+                Ok(roman)
+            }
+        }
+
+        // An integer in any radix from 2 to 36, optionally with the `0b`,
+        // `0o` or `0x` prefix and with digits grouped from the right.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct Radix {
+            value: i128,
+            radix: u32,
+            prefix: bool,
+            group: Option<(usize, char)>,
+            uppercase: bool,
+        }
+
+        impl Radix {
+            // `None` unless `radix` is between 2 and 36.
+            pub fn new(value: impl Into<i128>, radix: u32) -> Option<Radix> {
+                (2..=36).contains(&radix).then(|| Radix {
+                    value: value.into(),
+                    radix,
+                    prefix: false,
+                    group: None,
+                    uppercase: false,
+                })
+            }
+
+            // Only radixes 2, 8 and 16 have one; `{:#}` turns it on too.
+            pub fn prefix(mut self, prefix: bool) -> Radix {
+                self.prefix = prefix;
+                self
+            }
+
+            // `Radix::new(0xdeadbeef_u32, 16)?.group(4, '_')` gives
+            // `dead_beef`. A size of zero turns grouping off.
+            pub fn group(mut self, size: usize, separator: char) -> Radix {
+                self.group = (size != 0).then_some((size, separator));
+                self
+            }
+
+            pub fn uppercase(mut self, uppercase: bool) -> Radix {
+                self.uppercase = uppercase;
+                self
+            }
+        }
+
+        impl fmt::Display for Radix {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut rest = self.value.unsigned_abs();
+                let mut digits = Vec::new();
+                loop {
+                    let digit = char::from_digit((rest % self.radix as u128) as u32, self.radix)
+                        .expect("remainder is below the radix");
+                    digits.push(if self.uppercase {
+                        digit.to_ascii_uppercase()
+                    } else {
+                        digit
+                    });
+                    rest /= self.radix as u128;
+                    if rest == 0 {
+                        break;
+                    }
+                }
+                let digits: String = digits.iter().rev().collect();
+
+                let mut text = String::new();
+                if self.value < 0 {
+                    text.push('-');
+                }
+                if self.prefix || f.alternate() {
+                    text.push_str(match self.radix {
+                        2 => "0b",
+                        8 => "0o",
+                        16 => "0x",
+                        _ => "",
+                    });
+                }
+                match self.group {
+                    Some((size, separator)) => {
+                        text.push_str(&group_digits(&digits, size, separator))
+                    }
+                    None => text.push_str(&digits),
+                }
+                pad_aligned(f, &text, fmt::Alignment::Right)
+            }
+        }
+    }
+
+    // Format strings that arrive at runtime, say from a config file, rendered
+    // against any type that can look up its fields by name. The syntax is the
+    // one `format!` takes: `{name}: {lat:.3}°{ns}`, `{0:>8}`, `{:#x}`,
+    // `{value:0>width$}` and `{:.*}`, plus `{{` and `}}` for literal braces.
+    #[allow(dead_code)]
+    pub mod template {
+        use core::fmt;
+
+        use super::{City, MinMax, OwnedCity, Point2D};
+        use crate::chapters::chapter_18::error_handling::options_qmark::Person;
+
+        // A value a template can print.
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum Value {
+            // `bits` is the width of the original type, so `{:x}` on `-1i32`
+            // gives `ffffffff` as it does with `format!`.
+            Int { value: i64, bits: u32 },
+            UInt(u64),
+            Float(f64),
+            // Kept apart from `Float` so `0.1f32` still prints as `0.1`.
+            Float32(f32),
+            Text(String),
+            Char(char),
+            Bool(bool),
+            // An `Option` that was `None`: empty with `{}`, `None` with `{:?}`.
+            None,
+        }
+
+        macro_rules! signed_value {
+            ($($t:ty),*) => {
+                $(impl From<$t> for Value {
+                    fn from(value: $t) -> Value {
+                        Value::Int {
+                            value: value.into(),
+                            bits: <$t>::BITS,
+                        }
+                    }
+                })*
+            };
+        }
+
+        signed_value!(i8, i16, i32, i64);
+
+        impl From<u8> for Value {
+            fn from(value: u8) -> Value {
+                Value::UInt(value.into())
+            }
+        }
+
+        impl From<u32> for Value {
+            fn from(value: u32) -> Value {
+                Value::UInt(value.into())
+            }
+        }
+
+        impl From<u64> for Value {
+            fn from(value: u64) -> Value {
+                Value::UInt(value)
+            }
+        }
+
+        impl From<usize> for Value {
+            fn from(value: usize) -> Value {
+                Value::UInt(value as u64)
+            }
+        }
+
+        impl From<f32> for Value {
+            fn from(value: f32) -> Value {
+                Value::Float32(value)
+            }
+        }
+
+        impl From<f64> for Value {
+            fn from(value: f64) -> Value {
+                Value::Float(value)
+            }
+        }
+
+        impl From<&str> for Value {
+            fn from(value: &str) -> Value {
+                Value::Text(value.to_owned())
+            }
+        }
+
+        impl From<String> for Value {
+            fn from(value: String) -> Value {
+                Value::Text(value)
+            }
+        }
+
+        impl From<char> for Value {
+            fn from(value: char) -> Value {
+                Value::Char(value)
+            }
+        }
+
+        impl From<bool> for Value {
+            fn from(value: bool) -> Value {
+                Value::Bool(value)
+            }
+        }
+
+        impl<T: Into<Value>> From<Option<T>> for Value {
+            fn from(value: Option<T>) -> Value {
+                value.map_or(Value::None, Into::into)
+            }
+        }
+
+        impl Value {
+            // Widths and precisions taken from an argument must be integers.
+            fn as_count(&self) -> Option<usize> {
+                match *self {
+                    Value::Int { value, .. } => usize::try_from(value).ok(),
+                    Value::UInt(value) => usize::try_from(value).ok(),
+                    _ => None,
+                }
+            }
+        }
+
+        // What a template can ask a record for: `{name}` calls `field` and
+        // `{0}` or `{}` call `positional`.
+        pub trait Fields {
+            fn field(&self, name: &str) -> Option<Value>;
+
+            fn positional(&self, _index: usize) -> Option<Value> {
+                None
+            }
+        }
+
+        // Positional arguments only.
+        impl Fields for [Value] {
+            fn field(&self, _name: &str) -> Option<Value> {
+                None
+            }
+
+            fn positional(&self, index: usize) -> Option<Value> {
+                self.get(index).cloned()
+            }
+        }
+
+        // Named arguments, which like `format!`'s can also be used by
+        // position.
+        impl Fields for [(&str, Value)] {
+            fn field(&self, name: &str) -> Option<Value> {
+                self.iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, v)| v.clone())
+            }
+
+            fn positional(&self, index: usize) -> Option<Value> {
+                self.get(index).map(|(_, v)| v.clone())
+            }
+        }
+
+        // `lat` and `lon` are unsigned, as `City`'s `Display` writes them,
+        // with the hemisphere in `ns` and `ew`; `latitude` and `longitude`
+        // keep their sign.
+        impl Fields for City {
+            fn field(&self, name: &str) -> Option<Value> {
+                city_field(self.name, self.lat, self.lon, name)
+            }
+        }
+
+        impl Fields for OwnedCity {
+            fn field(&self, name: &str) -> Option<Value> {
+                city_field(&self.name, self.lat, self.lon, name)
+            }
+        }
+
+        fn city_field(city: &str, lat: f32, lon: f32, name: &str) -> Option<Value> {
+            let value = match name {
+                "name" => city.into(),
+                "lat" => lat.abs().into(),
+                "lon" => lon.abs().into(),
+                // The sign bit, as `City`'s `Display` uses.
+                "ns" => if lat.is_sign_negative() { 'S' } else { 'N' }.into(),
+                "ew" => if lon.is_sign_negative() { 'W' } else { 'E' }.into(),
+                "latitude" => lat.into(),
+                "longitude" => lon.into(),
+                _ => return None,
+            };
+            Some(value)
+        }
+
+        // `{min}` and `{max}`, or `{0}` and `{1}` as for the tuple struct.
+        impl Fields for MinMax {
+            fn field(&self, name: &str) -> Option<Value> {
+                match name {
+                    "min" => Some(self.min().into()),
+                    "max" => Some(self.max().into()),
+                    _ => None,
+                }
+            }
+
+            fn positional(&self, index: usize) -> Option<Value> {
+                match index {
+                    0 => Some(self.min().into()),
+                    1 => Some(self.max().into()),
+                    _ => None,
+                }
+            }
+        }
+
+        impl Fields for Point2D {
+            fn field(&self, name: &str) -> Option<Value> {
+                match name {
+                    "x" => Some(self.x().into()),
+                    "y" => Some(self.y().into()),
+                    _ => None,
+                }
+            }
+        }
+
+        // Fields are reached through the same path as in code, so
+        // `{job.phone_number.area_code}`. A missing link gives `Value::None`.
+        impl Fields for Person {
+            fn field(&self, name: &str) -> Option<Value> {
+                let phone = self.job.and_then(|job| job.phone_number);
+                let value = match name {
+                    "job" => Value::Bool(self.job.is_some()),
+                    "job.phone_number" => Value::Bool(phone.is_some()),
+                    "job.phone_number.area_code" => phone.and_then(|p| p.area_code).into(),
+                    "job.phone_number.number" => phone.map(|p| p.number).into(),
+                    _ => return None,
+                };
+                Some(value)
+            }
+        }
+
+        // `column` counts characters from 1, so it lines up with an editor.
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct TemplateError {
+            pub column: usize,
+            pub kind: TemplateErrorKind,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum TemplateErrorKind {
+            // A `{` with no `}`.
+            Unclosed,
+            // A lone `}`; write `}}` for a literal one.
+            UnmatchedClose,
+            Unexpected(char),
+            UnknownField(String),
+            MissingArgument(usize),
+            // A `width$` or `.precision$` argument that isn't an integer.
+            NotACount,
+            // A value that can't take the requested type, such as `{:x}` on
+            // a string.
+            Unsupported(char),
+        }
+
+        impl fmt::Display for TemplateError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "column {}: ", self.column)?;
+                match &self.kind {
+                    TemplateErrorKind::Unclosed => write!(f, "unclosed `{{`"),
+                    TemplateErrorKind::UnmatchedClose => write!(f, "unmatched `}}`"),
+                    TemplateErrorKind::Unexpected(c) => write!(f, "unexpected `{}`", c),
+                    TemplateErrorKind::UnknownField(name) => write!(f, "no field named `{}`", name),
+                    TemplateErrorKind::MissingArgument(index) => write!(f, "no argument {}", index),
+                    TemplateErrorKind::NotACount => {
+                        write!(f, "width and precision must be integers")
+                    }
+                    TemplateErrorKind::Unsupported(kind) => {
+                        write!(f, "value can't be formatted with `{}`", kind)
+                    }
+                }
+            }
+        }
+
+        impl std::error::Error for TemplateError {}
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        enum Argument {
+            Next,
+            Index(usize),
+            Name(String),
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        enum Count {
+            Literal(usize),
+            Argument(Argument),
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        struct Spec {
+            argument: Argument,
+            fill: char,
+            align: Option<fmt::Alignment>,
+            plus: bool,
+            alternate: bool,
+            zero: bool,
+            width: Option<Count>,
+            precision: Option<Count>,
+            // One of `' '` (plain `Display`), `?`, `b`, `o`, `x`, `X`, `e`, `E`.
+            kind: char,
+            column: usize,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        enum Piece {
+            Text(String),
+            Field(Spec),
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct Template {
+            pieces: Vec<Piece>,
+        }
+
+        // The template, one `char` at a time, with the column of each.
+        struct Chars {
+            chars: Vec<char>,
+            pos: usize,
+        }
+
+        impl Chars {
+            fn peek(&self) -> Option<char> {
+                self.chars.get(self.pos).copied()
+            }
+
+            fn peek_at(&self, ahead: usize) -> Option<char> {
+                self.chars.get(self.pos + ahead).copied()
+            }
+
+            fn eat(&mut self, c: char) -> bool {
+                if self.peek() == Some(c) {
+                    self.pos += 1;
+                    true
+                } else {
+                    false
+                }
+            }
+
+            fn column(&self) -> usize {
+                self.pos + 1
+            }
+
+            fn error(&self, kind: TemplateErrorKind) -> TemplateError {
+                TemplateError {
+                    column: self.column(),
+                    kind,
+                }
+            }
+
+            fn unexpected(&self) -> TemplateError {
+                match self.peek() {
+                    Some(c) => self.error(TemplateErrorKind::Unexpected(c)),
+                    None => self.error(TemplateErrorKind::Unclosed),
+                }
+            }
+
+            fn integer(&mut self) -> Option<usize> {
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    self.pos += 1;
+                }
+                let digits: String = self.chars[start..self.pos].iter().collect();
+                digits.parse().ok()
+            }
+
+            // Field names may contain dots, as in `job.phone_number`.
+            fn identifier(&mut self) -> Option<String> {
+                if !self.peek().is_some_and(|c| c.is_alphabetic() || c == '_') {
+                    return None;
+                }
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.')
+                {
+                    self.pos += 1;
+                }
+                Some(self.chars[start..self.pos].iter().collect())
+            }
+
+            fn argument(&mut self) -> Argument {
+                if let Some(index) = self.integer() {
+                    Argument::Index(index)
+                } else if let Some(name) = self.identifier() {
+                    Argument::Name(name)
+                } else {
+                    Argument::Next
+                }
+            }
+
+            // `5`, `width$` or `1$`. Leaves the cursor alone if there is no
+            // count here.
+            fn count(&mut self) -> Option<Count> {
+                let start = self.pos;
+                let argument = self.argument();
+                if self.eat('$') && argument != Argument::Next {
+                    return Some(Count::Argument(argument));
+                }
+                match argument {
+                    Argument::Index(value) => Some(Count::Literal(value)),
+                    _ => {
+                        self.pos = start;
+                        None
+                    }
+                }
+            }
+        }
+
+        impl Template {
+            pub fn parse(template: &str) -> Result<Template, TemplateError> {
+                let mut chars = Chars {
+                    chars: template.chars().collect(),
+                    pos: 0,
+                };
+                let mut pieces = Vec::new();
+                let mut text = String::new();
+
+                while let Some(c) = chars.peek() {
+                    if c == '}' {
+                        if chars.peek_at(1) != Some('}') {
+                            return Err(chars.error(TemplateErrorKind::UnmatchedClose));
+                        }
+                        chars.pos += 2;
+                        text.push('}');
+                    } else if c == '{' && chars.peek_at(1) == Some('{') {
+                        chars.pos += 2;
+                        text.push('{');
+                    } else if c == '{' {
+                        if !text.is_empty() {
+                            pieces.push(Piece::Text(std::mem::take(&mut text)));
+                        }
+                        pieces.push(Piece::Field(Template::spec(&mut chars)?));
+                    } else {
+                        chars.pos += 1;
+                        text.push(c);
+                    }
+                }
+                if !text.is_empty() {
+                    pieces.push(Piece::Text(text));
+                }
+                Ok(Template { pieces })
+            }
+
+            // `{argument:[[fill]align][+][#][0][width][.precision][type]}`
+            fn spec(chars: &mut Chars) -> Result<Spec, TemplateError> {
+                let column = chars.column();
+                chars.pos += 1;
+                let mut spec = Spec {
+                    argument: chars.argument(),
+                    fill: ' ',
+                    align: None,
+                    plus: false,
+                    alternate: false,
+                    zero: false,
+                    width: None,
+                    precision: None,
+                    kind: ' ',
+                    column,
+                };
+
+                if chars.eat(':') {
+                    let align = |c| match c {
+                        Some('<') => Some(fmt::Alignment::Left),
+                        Some('^') => Some(fmt::Alignment::Center),
+                        Some('>') => Some(fmt::Alignment::Right),
+                        _ => None,
+                    };
+                    if let (Some(fill), Some(alignment)) = (chars.peek(), align(chars.peek_at(1))) {
+                        spec.fill = fill;
+                        spec.align = Some(alignment);
+                        chars.pos += 2;
+                    } else if let Some(alignment) = align(chars.peek()) {
+                        spec.align = Some(alignment);
+                        chars.pos += 1;
+                    }
+
+                    spec.plus = chars.eat('+');
+                    if !spec.plus {
+                        // `-` is accepted but, as in `format!`, does nothing.
+                        chars.eat('-');
+                    }
+                    spec.alternate = chars.eat('#');
+                    if chars.peek() == Some('0') && chars.peek_at(1) != Some('$') {
+                        spec.zero = true;
+                        chars.pos += 1;
+                    }
+                    spec.width = chars.count();
+                    if chars.eat('.') {
+                        spec.precision = if chars.eat('*') {
+                            // Takes the next positional argument, before the
+                            // value's own.
+                            Some(Count::Argument(Argument::Next))
+                        } else {
+                            Some(chars.count().ok_or_else(|| chars.unexpected())?)
+                        };
+                    }
+                    if let Some(kind @ ('?' | 'b' | 'o' | 'x' | 'X' | 'e' | 'E')) = chars.peek() {
+                        spec.kind = kind;
+                        chars.pos += 1;
+                    }
+                }
+
+                while chars.peek().is_some_and(char::is_whitespace) {
+                    chars.pos += 1;
+                }
+                if !chars.eat('}') {
+                    return Err(chars.unexpected());
+                }
+                Ok(spec)
+            }
+
+            pub fn render<F: Fields + ?Sized>(&self, fields: &F) -> Result<String, TemplateError> {
+                let mut out = String::new();
+                let mut next = 0;
+                for piece in &self.pieces {
+                    let spec = match piece {
+                        Piece::Text(text) => {
+                            out.push_str(text);
+                            continue;
+                        }
+                        Piece::Field(spec) => spec,
+                    };
+
+                    let mut lookup = |argument: &Argument| {
+                        let index = match argument {
+                            Argument::Name(name) => {
+                                return fields.field(name).ok_or_else(|| TemplateError {
+                                    column: spec.column,
+                                    kind: TemplateErrorKind::UnknownField(name.clone()),
+                                });
+                            }
+                            Argument::Index(index) => *index,
+                            Argument::Next => {
+                                next += 1;
+                                next - 1
+                            }
+                        };
+                        fields.positional(index).ok_or(TemplateError {
+                            column: spec.column,
+                            kind: TemplateErrorKind::MissingArgument(index),
+                        })
+                    };
+                    let mut count = |count: &Option<Count>| match count {
+                        None => Ok(None),
+                        Some(Count::Literal(value)) => Ok(Some(*value)),
+                        Some(Count::Argument(argument)) => {
+                            lookup(argument)?.as_count().map(Some).ok_or(TemplateError {
+                                column: spec.column,
+                                kind: TemplateErrorKind::NotACount,
+                            })
+                        }
+                    };
+
+                    let width = count(&spec.width)?;
+                    let precision = count(&spec.precision)?;
+                    let value = lookup(&spec.argument)?;
+                    out.push_str(&format_value(&value, spec, width, precision)?);
+                }
+                Ok(out)
+            }
+        }
+
+        impl std::str::FromStr for Template {
+            type Err = TemplateError;
+
+            fn from_str(s: &str) -> Result<Template, TemplateError> {
+                Template::parse(s)
+            }
+        }
+
+        // Formats one value the way `format!` would for the same spec.
+        fn format_value(
+            value: &Value,
+            spec: &Spec,
+            width: Option<usize>,
+            precision: Option<usize>,
+        ) -> Result<String, TemplateError> {
+            let unsupported = TemplateError {
+                column: spec.column,
+                kind: TemplateErrorKind::Unsupported(spec.kind),
+            };
+            let plus = if spec.plus { "+" } else { "" };
+
+            // Sign, radix prefix and digits are kept apart so zero padding
+            // can go between them.
+            let (sign, prefix, body, numeric) = match (value, spec.kind) {
+                (Value::Int { value: v, .. }, ' ' | '?') => (
+                    if *v < 0 { "-" } else { plus },
+                    "",
+                    v.unsigned_abs().to_string(),
+                    true,
+                ),
+                (Value::UInt(v), ' ' | '?') => (plus, "", v.to_string(), true),
+                (Value::Float(v), ' ' | '?') => {
+                    float_text(*v, v.is_sign_negative(), v.is_nan(), spec, precision)
+                }
+                (Value::Float32(v), ' ' | '?') => {
+                    float_text(*v, v.is_sign_negative(), v.is_nan(), spec, precision)
+                }
+
+                // Negative integers show their two's complement at the width
+                // of the type they came from.
+                (Value::Int { .. } | Value::UInt(_), kind @ ('b' | 'o' | 'x' | 'X')) => {
+                    let bits = match *value {
+                        Value::Int { value, bits } => (value as u64) & (u64::MAX >> (64 - bits)),
+                        Value::UInt(v) => v,
+                        _ => unreachable!(),
+                    };
+                    let (prefix, body) = match kind {
+                        'b' => ("0b", format!("{:b}", bits)),
+                        'o' => ("0o", format!("{:o}", bits)),
+                        'x' => ("0x", format!("{:x}", bits)),
+                        _ => ("0x", format!("{:X}", bits)),
+                    };
+                    (plus, if spec.alternate { prefix } else { "" }, body, true)
+                }
+
+                (Value::Int { value: v, .. }, kind @ ('e' | 'E')) => {
+                    let sign = if *v < 0 { "-" } else { plus };
+                    (sign, "", exponent(v.unsigned_abs(), kind, precision), true)
+                }
+                (Value::UInt(v), kind @ ('e' | 'E')) => {
+                    (plus, "", exponent(*v, kind, precision), true)
+                }
+                (Value::Float(v), kind @ ('e' | 'E')) => {
+                    let sign = if v.is_sign_negative() && !v.is_nan() {
+                        "-"
+                    } else {
+                        plus
+                    };
+                    (sign, "", exponent(v.abs(), kind, precision), true)
+                }
+                (Value::Float32(v), kind @ ('e' | 'E')) => {
+                    let sign = if v.is_sign_negative() && !v.is_nan() {
+                        "-"
+                    } else {
+                        plus
+                    };
+                    (sign, "", exponent(v.abs(), kind, precision), true)
+                }
+
+                (Value::Text(text), ' ') => (
+                    "",
+                    "",
+                    // On text, precision is a maximum length.
+                    match precision {
+                        Some(max) => text.chars().take(max).collect(),
+                        None => text.clone(),
+                    },
+                    false,
+                ),
+                (Value::Text(text), '?') => ("", "", format!("{:?}", text), false),
+                (Value::Char(c), ' ') => ("", "", c.to_string(), false),
+                (Value::Char(c), '?') => ("", "", format!("{:?}", c), false),
+                (Value::Bool(b), ' ' | '?') => ("", "", b.to_string(), false),
+                (Value::None, ' ') => ("", "", String::new(), false),
+                (Value::None, '?') => ("", "", "None".to_owned(), false),
+                _ => return Err(unsupported),
+            };
+
+            let len = sign.chars().count() + prefix.len() + body.chars().count();
+            let padding = width.unwrap_or(0).saturating_sub(len);
+            if spec.zero && numeric {
+                // Zeros go after the sign and prefix, and fill and alignment
+                // are ignored, as with `format!`.
+                return Ok(format!("{}{}{}{}", sign, prefix, "0".repeat(padding), body));
+            }
+
+            let default = if numeric {
+                fmt::Alignment::Right
+            } else {
+                fmt::Alignment::Left
+            };
+            let (before, after) = match spec.align.unwrap_or(default) {
+                fmt::Alignment::Left => (0, padding),
+                fmt::Alignment::Right => (padding, 0),
+                fmt::Alignment::Center => (padding / 2, padding - padding / 2),
+            };
+            let fill = spec.fill.to_string();
+            Ok(format!(
+                "{}{}{}{}{}",
+                fill.repeat(before),
+                sign,
+                prefix,
+                body,
+                fill.repeat(after)
+            ))
+        }
+
+        fn float_text<T: fmt::Display + fmt::Debug + Copy + std::ops::Neg<Output = T>>(
+            value: T,
+            negative: bool,
+            nan: bool,
+            spec: &Spec,
+            precision: Option<usize>,
+        ) -> (&'static str, &'static str, String, bool) {
+            let magnitude = if negative { -value } else { value };
+            let body = match (precision, spec.kind) {
+                (Some(precision), _) => format!("{:.*}", precision, magnitude),
+                (None, '?') => format!("{:?}", magnitude),
+                (None, _) => magnitude.to_string(),
+            };
+            let sign = if negative && !nan {
+                "-"
+            } else if spec.plus {
+                "+"
+            } else {
+                ""
+            };
+            (sign, "", body, true)
+        }
+
+        fn exponent<T: fmt::LowerExp + fmt::UpperExp>(
+            value: T,
+            kind: char,
+            precision: Option<usize>,
+        ) -> String {
+            match (kind, precision) {
+                ('e', Some(precision)) => format!("{:.*e}", precision, value),
+                ('e', None) => format!("{:e}", value),
+                (_, Some(precision)) => format!("{:.*E}", precision, value),
+                (_, None) => format!("{:E}", value),
+            }
+        }
+    }
+
+    // `Debug` output that is safe to log. A type describes itself once as a
+    // tree of `Node`s through `Inspect`, marking the fields that must never
+    // be printed, and `DebugOptions` decides how much of that tree to show:
+    //
+    //     Person { job: Some(Job { phone_number: Some(PhoneNumber { area_code: Some(61), number: "***2222" }) }) }
+    //     Person { job: Some(Job { .. }) }
+    #[allow(dead_code)]
+    pub mod redact {
+        use core::fmt;
+
+        use super::{City, OwnedCity};
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum Node {
+            // The `Debug` text of a leaf value.
+            Value(String),
+            // A leaf that is always masked, keeping at most `tail` of its
+            // last characters.
+            Secret { text: String, tail: usize },
+            Some(Box<Node>),
+            None,
+            Struct(&'static str, Vec<(&'static str, Node)>),
+        }
+
+        impl Node {
+            pub fn value<T: fmt::Debug + ?Sized>(value: &T) -> Node {
+                Node::Value(format!("{:?}", value))
+            }
+
+            pub fn secret<T: fmt::Debug + ?Sized>(value: &T) -> Node {
+                Node::secret_tail(value, 0)
+            }
+
+            pub fn secret_tail<T: fmt::Debug + ?Sized>(value: &T, tail: usize) -> Node {
+                Node::Secret {
+                    text: format!("{:?}", value),
+                    tail,
+                }
+            }
+
+            // Starts a record; add fields and `finish` it, as with
+            // `debug_struct`.
+            pub fn record(name: &'static str) -> Record {
+                Record {
+                    name,
+                    fields: Vec::new(),
+                }
+            }
+        }
+
+        // A `Node::Struct` being built. Only records have fields, so the
+        // field methods live here rather than on `Node`.
+        pub struct Record {
+            name: &'static str,
+            fields: Vec<(&'static str, Node)>,
+        }
+
+        impl Record {
+            pub fn field<T: Inspect + ?Sized>(self, name: &'static str, value: &T) -> Record {
+                self.with(name, value.inspect())
+            }
+
+            pub fn secret_field<T: fmt::Debug + ?Sized>(
+                self,
+                name: &'static str,
+                value: &T,
+            ) -> Record {
+                self.with(name, Node::secret(value))
+            }
+
+            // Like `secret_field`, but keeps the last `tail` characters so a
+            // value can still be told apart, e.g. `***6969`.
+            pub fn secret_field_tail<T: fmt::Debug + ?Sized>(
+                self,
+                name: &'static str,
+                value: &T,
+                tail: usize,
+            ) -> Record {
+                self.with(name, Node::secret_tail(value, tail))
+            }
+
+            pub fn finish(self) -> Node {
+                Node::Struct(self.name, self.fields)
+            }
+
+            fn with(mut self, name: &'static str, node: Node) -> Record {
+                self.fields.push((name, node));
+                self
+            }
+        }
+
+        pub trait Inspect {
+            fn inspect(&self) -> Node;
+
+            // Shorthand for `DebugOptions::default().show(self)`.
+            fn redacted(&self) -> Redacted<'_, Self> {
+                Redacted {
+                    value: self,
+                    options: DebugOptions::default(),
+                }
+            }
+        }
+
+        macro_rules! inspect_as_value {
+            ($($t:ty),*) => {
+                $(impl Inspect for $t {
+                    fn inspect(&self) -> Node {
+                        Node::value(self)
+                    }
+                })*
+            };
+        }
+
+        inspect_as_value!(
+            bool, char, i8, i16, i32, i64, u8, u16, u32, u64, usize, f32, f64, str, String
+        );
+
+        impl<T: Inspect + ?Sized> Inspect for &T {
+            fn inspect(&self) -> Node {
+                (**self).inspect()
+            }
+        }
+
+        impl<T: Inspect> Inspect for Option<T> {
+            fn inspect(&self) -> Node {
+                match self {
+                    Some(value) => Node::Some(Box::new(value.inspect())),
+                    None => Node::None,
+                }
+            }
+        }
+
+        impl Inspect for City {
+            fn inspect(&self) -> Node {
+                Node::record("City")
+                    .field("name", self.name)
+                    .field("lat", &self.lat)
+                    .field("lon", &self.lon)
+                    .finish()
+            }
+        }
+
+        impl Inspect for OwnedCity {
+            fn inspect(&self) -> Node {
+                Node::record("OwnedCity")
+                    .field("name", &self.name)
+                    .field("lat", &self.lat)
+                    .field("lon", &self.lon)
+                    .finish()
+            }
+        }
+
+        // The default shows everything, in declaration order, with secrets
+        // keeping only the tail their field asks for.
+        #[derive(Debug, Clone, PartialEq, Default)]
+        pub struct DebugOptions {
+            max_depth: Option<usize>,
+            hide_none: bool,
+            sort_keys: bool,
+            redact: Vec<&'static str>,
+        }
+
+        impl DebugOptions {
+            pub fn new() -> DebugOptions {
+                DebugOptions::default()
+            }
+
+            // How many records deep to print; anything deeper becomes
+            // `Name { .. }`. A depth of 0 elides even the outermost record.
+            pub fn max_depth(mut self, depth: usize) -> DebugOptions {
+                self.max_depth = Some(depth);
+                self
+            }
+
+            // Leave out fields that are `None` instead of printing them.
+            pub fn hide_none(mut self, hide: bool) -> DebugOptions {
+                self.hide_none = hide;
+                self
+            }
+
+            // Print fields alphabetically rather than in declaration order.
+            pub fn sort_keys(mut self, sort: bool) -> DebugOptions {
+                self.sort_keys = sort;
+                self
+            }
+
+            // Treats every field with this name as a secret, at any depth. A
+            // redacted field is masked as a whole, whether it's a record, an
+            // `Option` or a secret with a tail, and is never hidden as `None`.
+            pub fn redact(mut self, field: &'static str) -> DebugOptions {
+                self.redact.push(field);
+                self
+            }
+
+            pub fn show<'a, T: Inspect + ?Sized>(&self, value: &'a T) -> Redacted<'a, T> {
+                Redacted {
+                    value,
+                    options: self.clone(),
+                }
+            }
+        }
+
+        fn mask(text: &str, tail: usize) -> String {
+            let raw = text.trim_matches('"');
+            let len = raw.chars().count();
+            // Showing the tail of a short secret would show all of it.
+            let keep = if len > tail { tail } else { 0 };
+            let tail: String = raw.chars().skip(len - keep).collect();
+            format!("***{}", tail)
+        }
+
+        pub struct Redacted<'a, T: ?Sized> {
+            value: &'a T,
+            options: DebugOptions,
+        }
+
+        impl<T: Inspect + ?Sized> fmt::Debug for Redacted<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let node = self.value.inspect();
+                let view = View {
+                    node: &node,
+                    options: &self.options,
+                    depth: 0,
+                    secret: false,
+                };
+                fmt::Debug::fmt(&view, f)
+            }
+        }
+
+        // One node being printed. Going through `debug_struct` means `{:#?}`
+        // pretty-prints exactly like a derived `Debug`.
+        struct View<'a> {
+            node: &'a Node,
+            options: &'a DebugOptions,
+            depth: usize,
+            secret: bool,
+        }
+
+        impl View<'_> {
+            fn child<'b>(&'b self, node: &'b Node, secret: bool) -> View<'b> {
+                View {
+                    node,
+                    options: self.options,
+                    depth: self.depth,
+                    secret: self.secret || secret,
+                }
+            }
+        }
+
+        impl fmt::Debug for View<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self.node {
+                    _ if self.secret => fmt::Debug::fmt("***", f),
+                    Node::Secret { text, tail } => fmt::Debug::fmt(&mask(text, *tail), f),
+                    Node::Value(text) => f.write_str(text),
+                    Node::None => f.write_str("None"),
+                    Node::Some(inner) => f
+                        .debug_tuple("Some")
+                        .field(&self.child(inner, false))
+                        .finish(),
+                    Node::Struct(name, _)
+                        if self.options.max_depth.is_some_and(|max| self.depth >= max) =>
+                    {
+                        f.debug_struct(name).finish_non_exhaustive()
+                    }
+                    Node::Struct(name, fields) => {
+                        let mut fields: Vec<_> = fields
+                            .iter()
+                            .filter(|(key, node)| {
+                                !(self.options.hide_none
+                                    && *node == Node::None
+                                    && !self.options.redact.contains(key))
+                            })
+                            .collect();
+                        if self.options.sort_keys {
+                            fields.sort_by_key(|(key, _)| *key);
+                        }
+
+                        let mut out = f.debug_struct(name);
+                        for (key, node) in fields {
+                            let mut view = self.child(node, self.options.redact.contains(key));
+                            view.depth += 1;
+                            out.field(key, &view);
+                        }
+                        out.finish()
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::chapters::chapter_1::chapter_1::City;
+    use crate::chapters::chapter_18::error_handling::options_qmark::{Job, Person, PhoneNumber};
+
+    use super::chapter_1::{
+        accumulator::{MinMaxOf, NanPolicy},
+        color::{ColorDepth, Hsl, Hsv, Rgb},
+        gazetteer::{fold, BoundingBox, Gazetteer, RowError, RowErrorKind},
+        geodesy::{Kilometres, LatLon, Metres, NauticalMiles},
+        geohash::{group_by_cell, Direction, Geohash, GeohashError},
+        interval_set::IntervalSet,
+        notation::{Notation, Utm},
+        numbers::{BinaryPrefix, Grouped, Locale, Ordinal, Radix, Roman, SiPrefix},
+        parse::{Expected, ParseError},
+        redact::{DebugOptions, Inspect, Node},
+        table::{display_width, Align, Cell, Column, RowLengthError, Style, Table},
+        template::{Template, TemplateError, TemplateErrorKind, Value},
+        ArithmeticError, BoundsError, Complex, List, ListStyle, MinMax, OwnedCity, Point2D,
+    };
+
+    #[test]
+    fn formart_print() {
+        // In general, the `{}` will be automatically replaced with any
+        // arguments. This will be stringfied.
+
+        let str = format!("{} days", 31);
+
+        assert_eq!("31 days", str);
+
+        // Positional arguments can be used . Specfiying an integer `{}`
+        // Determines which additiona; arguments will be replaced. Areguments starts
+        // at 0 immediately adfer the format string.
+        let str = format!("{0}, this is {1}. {1}, this is {0}", "Alice", "Bob");
+        assert_eq!("Alice, this is Bob. Bob, this is Alice", str);
+
+        // As can named arguments.
+        let str: String = format!(
+            "{subject} {verb} {object}",
+            object = "the lazy dog",
+            subject = "the quick brown fox",
+            verb = "jumps over"
+        );
+
+        assert_eq!("the quick brown fox jumps over the lazy dog", str);
+
+        // different formatting can be invocked by specifying the format character
+        // after a `:`
+        let base_10 = format!("Base 10: {}", 69420);
+        let base_2 = format!("Base 2 (binary): {:b}", 69420);
+        let base_8 = format!("Base 8 (octal): {:o}", 69420);
+        let base_16 = format!("Base 16 (hexadecimal): {:x}", 69420);
+        let base_16_x = format!("Base 16 (hexadecimal): {:X}", 69420);
+
+        assert_eq!("Base 10: 69420", base_10);
+        assert_eq!("Base 2 (binary): 10000111100101100", base_2);
+        assert_eq!("Base 8 (octal): 207454", base_8);
+        assert_eq!("Base 16 (hexadecimal): 10f2c", base_16);
+        assert_eq!("Base 16 (hexadecimal): 10F2C", base_16_x);
+
+        // You can right justify text with a specified width. This will
+        // output "    1". (Four white space and a "1", for a total width of 5.)
+        let str = format!("{number:>5}", number = 1);
+        assert_eq!("    1", str);
+
+        // you can pad nmbers with extra zeroes,
+        let str = format!("{number:0>5}", number = 1);
+        assert_eq!("00001", str);
+
+        // You can use named arguments in thr format specifier by appending a '$'
+        let str = format!("{number:0>width$}", number = 1, width = 5);
+        assert_eq!("00001", str);
+
+        // Rust even checks to make sure the correct number of arguments are used.
+        println!("My name is {0}, {1} {0}", "Bond", "James");
+        let pi = 3.141592;
+
+        // Add a println! macro call that prints: Pi is roughly 3.142 by controlling
+        // the number of decimal places shown. For the purposes of this exercise,
+        //use let pi = 3.141592 as an estimate for pi.
+        let str = format!("{pi:.3}");
+        assert_eq!("3.142", str);
+    }
+
+    #[test]
+    fn format_display() {
+        let v: List = List(vec![1, 2, 3]);
+        let str: String = format!("{v}");
+        assert_eq!("[0: 1, 1: 2, 2: 3]", str);
+    }
+
+    #[test]
+    fn format_specifier() {
+        let city1 = City { name: "Dublin", lat: 53.347778, lon: -6.259722 };
+        let city2 = City { name: "Oslo", lat: 59.95, lon: 10.75 };
+        let city3 = City { name: "Vancouver", lat: 49.25, lon: -123.1 };
+
+        assert_eq!("Dublin: 53.348°N 6.260°W", format!("{}", city1));
+        assert_eq!("Oslo: 59.950°N 10.750°E", format!("{}", city2));
+        assert_eq!("Vancouver: 49.250°N 123.100°W", format!("{}", city3));
+    }
+
+    #[test]
+    fn min_max_validation() {
+        assert_eq!(MinMax::new(-300, 300).unwrap().to_string(), "(-300, 300)");
+        assert_eq!(
+            MinMax::new(300, -300),
+            Err(BoundsError { min: 300, max: -300 })
+        );
+        assert_eq!(
+            MinMax::new(300, -300).unwrap_err().to_string(),
+            "invalid range: min 300 is greater than max -300"
+        );
+    }
+
+    #[test]
+    fn min_max_interval_ops() {
+        let big = MinMax::new(-300, 300).unwrap();
+        let small = MinMax::new(-3, 3).unwrap();
+        let right = MinMax::new(250, 400).unwrap();
+        let far = MinMax::new(500, 600).unwrap();
+
+        assert!(big.contains(-300) && big.contains(300) && !big.contains(301));
+        assert!(big.overlaps(&small) && big.overlaps(&right) && !big.overlaps(&far));
+        assert_eq!(big.intersection(&right), MinMax::new(250, 300).ok());
+        assert_eq!(big.intersection(&far), None);
+        assert_eq!(small.hull(&far), MinMax::new(-3, 600).unwrap());
+
+        assert_eq!(big.len(), 600);
+        assert_eq!(MinMax::new(i64::MIN, i64::MAX).unwrap().len(), u64::MAX);
+        assert!(MinMax::new(7, 7).unwrap().is_degenerate());
+        assert!(!small.is_degenerate());
+
+        assert_eq!(small.clamp(-10), -3);
+        assert_eq!(small.clamp(2), 2);
+        assert_eq!(small.clamp(10), 3);
+
+        assert_eq!(
+            small.split_at(0),
+            Some((MinMax::new(-3, -1).unwrap(), MinMax::new(0, 3).unwrap()))
+        );
+        assert_eq!(small.split_at(-3), None);
+        assert_eq!(small.split_at(4), None);
+    }
+
+    fn set(ranges: &[(i64, i64)]) -> IntervalSet {
+        ranges
+            .iter()
+            .map(|&(min, max)| MinMax::new(min, max).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn interval_set_insert_remove() {
+        let mut ranges = set(&[(10, 14), (0, 3), (4, 6), (20, 25)]);
+        assert_eq!("{(0, 6), (10, 14), (20, 25)}", ranges.to_string());
+
+        ranges.insert(MinMax::new(5, 21).unwrap());
+        assert_eq!("{(0, 25)}", ranges.to_string());
+
+        ranges.remove(MinMax::new(8, 11).unwrap());
+        ranges.remove(MinMax::new(25, 30).unwrap());
+        assert_eq!("{(0, 7), (12, 24)}", ranges.to_string());
+        assert_eq!(2, ranges.len());
+
+        assert!(ranges.contains(0) && ranges.contains(7) && ranges.contains(24));
+        assert!(!ranges.contains(8) && !ranges.contains(25) && !ranges.contains(-1));
+
+        let gaps: Vec<MinMax> = ranges.gaps().collect();
+        assert_eq!(vec![MinMax::new(8, 11).unwrap()], gaps);
+
+        assert_eq!("{}", IntervalSet::new().to_string());
+        let extremes = set(&[(i64::MIN, -1), (0, i64::MAX)]);
+        assert_eq!(1, extremes.len());
+    }
+
+    #[test]
+    fn interval_set_algebra() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (40, 50)]);
+
+        assert_eq!("{(0, 30), (40, 50)}", a.union(&b).to_string());
+        assert_eq!("{(5, 10), (20, 25)}", a.intersection(&b).to_string());
+        assert_eq!("{(0, 4), (26, 30)}", a.difference(&b).to_string());
+        assert_eq!("{(11, 19), (40, 50)}", b.difference(&a).to_string());
+    }
+
+    #[test]
+    fn min_max_arithmetic() {
+        let mm = |min, max| MinMax::new(min, max).unwrap();
+        let big = mm(-300, 300);
+        let small = mm(-3, 3);
+
+        assert_eq!(mm(-900, 900), small * big);
+        assert_eq!(mm(-303, 303), small + big);
+        assert_eq!(mm(-5, 8), mm(1, 5) - mm(-3, 6));
+        assert_eq!(mm(-5, -1), -mm(1, 5));
+        assert_eq!(mm(6, 20), mm(2, 4) * mm(3, 5));
+        assert_eq!(mm(-20, -6), mm(2, 4) * mm(-5, -3));
+
+        assert_eq!(Ok(mm(-100, 100)), big / mm(3, 10));
+        assert_eq!(Ok(mm(-10, -2)), mm(10, 20) / mm(-5, -2));
+        assert_eq!(Err(ArithmeticError::DivisionByZero), big / small);
+        assert_eq!(Err(ArithmeticError::DivisionByZero), big / mm(0, 4));
+
+        let huge = mm(0, i64::MAX);
+        assert_eq!(Err(ArithmeticError::Overflow), huge.checked_add(mm(1, 1)));
+        assert_eq!(Err(ArithmeticError::Overflow), huge.checked_mul(mm(-2, 2)));
+        assert_eq!(
+            Err(ArithmeticError::Overflow),
+            mm(i64::MIN, 0).checked_neg()
+        );
+        assert_eq!(
+            Err(ArithmeticError::Overflow),
+            mm(i64::MIN, 0).checked_div(mm(-1, -1))
+        );
+
+        assert_eq!(mm(1, i64::MAX), huge.saturating_add(mm(1, 1)));
+        assert_eq!(mm(i64::MIN, i64::MAX), huge.saturating_mul(mm(-2, 2)));
+        assert_eq!(mm(0, i64::MAX), mm(i64::MIN, 0).saturating_neg());
+        assert_eq!(mm(i64::MIN, -1), mm(i64::MIN, 0).saturating_sub(mm(1, 1)));
+        assert_eq!(
+            Ok(mm(0, i64::MAX)),
+            mm(i64::MIN, 0).saturating_div(mm(-1, -1))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn min_max_add_overflow_panics() {
+        let _ = MinMax::new(0, i64::MAX).unwrap() + MinMax::new(1, 1).unwrap();
+    }
+
+    #[test]
+    fn min_max_from_data() {
+        let values = [4, -2, 9, 0, 9, -2];
+        assert_eq!(MinMax::new(-2, 9).ok(), MinMax::from_values(values));
+        assert_eq!(None, MinMax::from_values(Vec::new()));
+
+        let collected: Option<MinMax> = values.into_iter().collect::<MinMaxOf<i64>>().into();
+        assert_eq!(MinMax::new(-2, 9).ok(), collected);
+
+        let mut range = MinMax::new(0, 14).unwrap();
+        range.extend([-3, 5, 20]);
+        assert_eq!("(-3, 20)", range.to_string());
+    }
+
+    #[test]
+    fn min_max_of_floats() {
+        let values = [2.5, f64::NAN, -1.0, 7.25, -1.0, 7.25];
+
+        let acc: MinMaxOf<f64> = values.iter().copied().collect();
+        assert_eq!((Some(&-1.0), Some(&7.25)), (acc.min(), acc.max()));
+        assert_eq!((Some(2), Some(3)), (acc.argmin(), acc.argmax()));
+        assert_eq!(6, acc.count());
+
+        let mut poisoned = MinMaxOf::with_nan_policy(NanPolicy::Propagate);
+        poisoned.extend(values);
+        assert!(poisoned.min().unwrap().is_nan() && poisoned.max().unwrap().is_nan());
+        assert_eq!(Some(1), poisoned.argmin());
+
+        // Reducing chunks independently gives the same answer as one pass.
+        let merged = values
+            .chunks(2)
+            .map(|chunk| chunk.iter().copied().collect::<MinMaxOf<f64>>())
+            .reduce(MinMaxOf::merge)
+            .unwrap();
+        assert_eq!((acc.min(), acc.max()), (merged.min(), merged.max()));
+        assert_eq!(
+            (Some(2), Some(3), 6),
+            (merged.argmin(), merged.argmax(), merged.count())
+        );
+
+        let empty: MinMaxOf<f64> = MinMaxOf::new();
+        assert_eq!((None, None), (empty.min(), empty.argmax()));
+    }
+
+    fn round_trip<T>(text: &str) -> T
+    where
+        T: std::str::FromStr<Err = ParseError> + std::fmt::Display,
+    {
+        let value: T = text.parse().unwrap();
+        assert_eq!(text, value.to_string());
+        value
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        assert_eq!(MinMax::new(0, 14).unwrap(), round_trip("(0, 14)"));
+        assert_eq!(MinMax::new(-300, -3).unwrap(), round_trip("(-300, -3)"));
+
+        assert_eq!(Point2D::new(4.5, 6.8), round_trip("x: 4.5, y: 6.8"));
+        assert_eq!(Point2D::new(-4.5, -0.25), round_trip("x: -4.5, y: -0.25"));
+        assert!(round_trip::<Point2D>("x: NaN, y: -inf")
+            .to_string()
+            .contains("NaN"));
+
+        assert_eq!(List(vec![1, 2, 3]), round_trip("[0: 1, 1: 2, 2: 3]"));
+        assert_eq!(List(vec![-7]), round_trip("[0: -7]"));
+        assert_eq!(List(vec![]), round_trip("[]"));
+
+        let dublin: City = round_trip("Dublin: 53.348°N 6.260°W");
+        assert_eq!("Dublin", dublin.name);
+        assert!(dublin.lat > 0.0 && dublin.lon < 0.0);
+
+        let sydney: City = round_trip("Sydney: 33.869°S 151.209°E");
+        assert!(sydney.lat < 0.0 && sydney.lon > 0.0);
+        let lima: City = round_trip("Lima: 12.046°S 77.043°W");
+        assert!(lima.lat < 0.0 && lima.lon < 0.0);
+        let oslo: City = round_trip("Oslo: 59.950°N 10.750°E");
+        assert_eq!((59.95, 10.75), (oslo.lat, oslo.lon));
+        assert_eq!("A: B", round_trip::<City>("A: B: 1.000°N 2.000°E").name);
+
+        // A hemisphere survives even when the value rounds to zero.
+        let null_island: City = round_trip("Null Island: 0.000°S 0.000°W");
+        assert!(null_island.lat.is_sign_negative() && null_island.lon.is_sign_negative());
+        let nearly = City {
+            name: "Nearly",
+            lat: -0.0001,
+            lon: 0.0001,
+        };
+        assert_eq!("Nearly: 0.000°S 0.000°E", nearly.to_string());
+        round_trip::<City>(&nearly.to_string());
+    }
+
+    fn err<T>(offset: usize, expected: Expected) -> Result<T, ParseError> {
+        Err(ParseError { offset, expected })
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(err(2, Expected::Text(", ")), "(0,14)".parse::<MinMax>());
+        assert_eq!(err(1, Expected::OrderedBounds), "(14, 0)".parse::<MinMax>());
+        assert_eq!(err(7, Expected::End), "(0, 14) ".parse::<MinMax>());
+        assert_eq!(err(1, Expected::Integer), "(a, 14)".parse::<MinMax>());
+
+        assert_eq!(err(11, Expected::Number), "x: 4.5, y: ?".parse::<Point2D>());
+
+        assert_eq!(err(7, Expected::Index(1)), "[0: 1, 2: 2]".parse::<List>());
+        assert_eq!(
+            err(5, Expected::OneOf(&[", ", "]"])),
+            "[0: 1; 1: 2]".parse::<List>()
+        );
+
+        assert_eq!(
+            err(16, Expected::OneOf(&["N", "S"])),
+            "Dublin: 53.348°E 6.260°W".parse::<City>()
+        );
+        assert_eq!(
+            err(8, Expected::UnsignedNumber),
+            "Dublin: -53.348°N 6.260°W".parse::<City>()
+        );
+        assert_eq!(err(0, Expected::Name), ": 1.000°N 2.000°E".parse::<City>());
+        assert_eq!(
+            err(3, Expected::Value("a latitude within ±90°")),
+            "X: 500.000°N 999.000°E".parse::<City>()
+        );
+        assert_eq!(
+            err(12, Expected::Value("a longitude within ±180°")),
+            "X: 5.000°N 999.000°E".parse::<City>()
+        );
+        assert_eq!(
+            err(3, Expected::Value("a latitude within ±90°")),
+            "X: NaN°N inf°E".parse::<City>()
+        );
+        assert_eq!(
+            err(12, Expected::Value("a longitude within ±180°")),
+            "X: 0.000°N inf°E".parse::<OwnedCity>()
+        );
+
+        assert_eq!(
+            "expected `N` or `S` at byte 16",
+            "Dublin: 53.348°E 6.260°W"
+                .parse::<OwnedCity>()
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn point_vector_ops() {
+        use std::f64::consts::{FRAC_PI_2, PI};
+
+        let a = Point2D::new(3.0, 4.0);
+        let b = Point2D::new(-1.0, 2.0);
+
+        assert_eq!((3.0, 4.0), (a.x(), a.y()));
+        assert_eq!(Point2D::new(2.0, 6.0), a + b);
+        assert_eq!(Point2D::new(4.0, 2.0), a - b);
+        assert_eq!(Point2D::new(-3.0, -4.0), -a);
+        assert_eq!(Point2D::new(6.0, 8.0), a * 2.0);
+        assert_eq!(Point2D::new(6.0, 8.0), 2.0 * a);
+        assert_eq!(Point2D::new(1.5, 2.0), a / 2.0);
+
+        assert_eq!(5.0, a.dot(b));
+        assert_eq!(10.0, a.cross(b));
+        assert_eq!(-10.0, b.cross(a));
+        assert_eq!(5.0, a.norm());
+        assert_eq!(5.0, Point2D::new(0.0, 0.0).distance(a));
+
+        assert_eq!(FRAC_PI_2, Point2D::new(0.0, 2.0).angle());
+        assert_eq!(PI, Point2D::new(-1.0, 0.0).angle());
+        assert!(Point2D::new(1.0, 0.0)
+            .rotate(FRAC_PI_2)
+            .approx_eq(Point2D::new(0.0, 1.0), 1e-12));
+        assert!(a.rotate(PI).approx_eq(-a, 1e-12));
+
+        assert_eq!(Point2D::new(1.0, 3.0), a.lerp(b, 0.5));
+        assert_eq!(a, a.lerp(b, 0.0));
+        assert_eq!(Some(Point2D::new(0.6, 0.8)), a.normalize());
+        assert_eq!(None, Point2D::new(0.0, 0.0).normalize());
+
+        assert!(a.approx_eq(Point2D::new(3.0 + 1e-10, 4.0), 1e-9));
+        assert!(!a.approx_eq(Point2D::new(3.1, 4.0), 1e-9));
+    }
+
+    #[test]
+    fn point_display_flags() {
+        let point = Point2D::new(4.5, -6.8);
+
+        assert_eq!("x: 4.5, y: -6.8", format!("{}", point));
+        assert_eq!("x: +4.50, y: -6.80", format!("{:+.2}", point));
+        assert_eq!("     x: 4.5, y: -6.8", format!("{:>20}", point));
+        assert_eq!("x: 4.5, y: -6.8*****", format!("{:*<20}", point));
+        assert_eq!("__x: 4.5, y: -6.8___", format!("{:_^20}", point));
+        // Precision rounds the coordinates instead of truncating the text.
+        assert_eq!("x: 4.5, y: -6.8", format!("{:.1}", point));
+        assert_eq!("x: 4.5\ny: -6.8", format!("{:#}", point));
+        assert_eq!("   x: 4.5\n  y: -6.8", format!("{:>#9}", point));
+    }
+
+    #[test]
+    fn point_bit_patterns() {
+        let point = Point2D::new(4.5, -0.0);
+
+        // 4.5 = 1.125 * 2^2: biased exponent 1025, mantissa 0.125.
+        assert_eq!(
+            "x: 0100000000010010000000000000000000000000000000000000000000000000, \
+             y: 1000000000000000000000000000000000000000000000000000000000000000",
+            format!("{:b}", point)
+        );
+        assert_eq!(
+            "x: [sign: 0, exponent: 10000000001, \
+             mantissa: 0010000000000000000000000000000000000000000000000000], \
+             y: [sign: 1, exponent: 00000000000, \
+             mantissa: 0000000000000000000000000000000000000000000000000000]",
+            format!("{:#b}", point)
+        );
+        assert_eq!(
+            "x: 0400220000000000000000, y: 1000000000000000000000",
+            format!("{:o}", point)
+        );
+        assert_eq!(
+            "x: 4012000000000000, y: 8000000000000000",
+            format!("{:x}", point)
+        );
+        assert_eq!(
+            "x: [sign: 0, exponent: 401, mantissa: 2000000000000], \
+             y: [sign: 1, exponent: 000, mantissa: 0000000000000]",
+            format!("{:#X}", point)
+        );
+
+        // Every bit survives, unlike the old `as i64` cast.
+        let odd = Point2D::new(0.1, f64::MIN_POSITIVE);
+        assert_eq!(
+            format!(
+                "x: {:016x}, y: {:016x}",
+                0.1f64.to_bits(),
+                f64::MIN_POSITIVE.to_bits()
+            ),
+            format!("{:x}", odd)
+        );
+    }
+
+    #[test]
+    fn point_exponent_forms() {
+        let point = Point2D::new(4.5, -1234.5);
+
+        assert_eq!("x: 4.5e0, y: -1.2345e3", format!("{:e}", point));
+        assert_eq!("x: +4.50E0, y: -1.23E3", format!("{:+.2E}", point));
+        assert_eq!(
+            "x: [sign: +, exponent: 2, mantissa: 1.125], \
+             y: [sign: -, exponent: 10, mantissa: 1.20556640625]",
+            format!("{:#e}", point)
+        );
+        assert_eq!(
+            "x: [sign: +, exponent: -1022, mantissa: 0], y: [sign: -, exponent: 1024, mantissa: inf]",
+            format!("{:#e}", Point2D::new(0.0, f64::NEG_INFINITY))
+        );
+    }
+
+    #[test]
+    fn city_geodesy() {
+        let dublin = City {
+            name: "Dublin",
+            lat: 53.34778,
+            lon: -6.259722,
+        };
+        let oslo = City {
+            name: "Oslo",
+            lat: 59.95,
+            lon: 10.75,
+        };
+        let vancouver = City {
+            name: "Vancouver",
+            lat: 49.25,
+            lon: -123.1,
+        };
+        let close = |a: f64, b: f64, tolerance: f64| (a - b).abs() <= tolerance;
+
+        // Reference values from the haversine and Vincenty formulae.
+        assert!(close(dublin.haversine_distance(&oslo).0, 1266.96, 0.01));
+        assert!(close(oslo.haversine_distance(&vancouver).0, 7180.68, 0.01));
+        assert!(close(
+            dublin.vincenty_distance(&oslo).unwrap().0,
+            1_270_478.195,
+            0.01
+        ));
+        assert!(close(
+            oslo.vincenty_distance(&vancouver).unwrap().0,
+            7_203_924.412,
+            0.01
+        ));
+        assert_eq!(Some(Metres(0.0)), oslo.vincenty_distance(&oslo));
+
+        assert!(close(dublin.initial_bearing(&oslo), 47.860, 0.001));
+        assert!(close(dublin.final_bearing(&oslo), 62.123, 0.001));
+        assert!(close(oslo.initial_bearing(&vancouver), 328.586, 0.001));
+        assert!(close(oslo.final_bearing(&vancouver), 203.568, 0.001));
+
+        // Going half way along the initial bearing lands on the midpoint.
+        let half = Kilometres(dublin.haversine_distance(&oslo).0 / 2.0);
+        let mid = dublin.midpoint(&oslo);
+        let there = dublin.destination(dublin.initial_bearing(&oslo), half);
+        assert!(close(mid.lat, there.lat, 1e-9) && close(mid.lon, there.lon, 1e-9));
+        assert_eq!("56.937°N 1.494°E", mid.to_string());
+
+        // Crossing the antimeridian wraps the longitude.
+        let east = City {
+            name: "East",
+            lat: 0.0,
+            lon: 179.0,
+        };
+        let LatLon { lon, .. } = east.destination(90.0, Kilometres(222.39));
+        assert!(close(lon, -179.0, 1e-3));
+
+        // Oslo lies north of, i.e. left of, an eastbound equator route.
+        let west = City {
+            name: "West",
+            lat: 0.0,
+            lon: 0.0,
+        };
+        let xtd = oslo.cross_track_distance(&west, &east);
+        assert!(close(
+            xtd.0,
+            -oslo
+                .haversine_distance(&City {
+                    lon: 10.75,
+                    lat: 0.0,
+                    ..west
+                })
+                .0,
+            1e-6
+        ));
+    }
+
+    #[test]
+    fn distance_units() {
+        let km = Kilometres(1.852);
+        assert_eq!(NauticalMiles(1.0), NauticalMiles::from(km));
+        assert_eq!(Metres(1852.0), Metres::from(km));
+        assert_eq!(Kilometres(1.5), Kilometres::from(Metres(1500.0)));
+        assert_eq!(Metres(3704.0), Metres::from(NauticalMiles(2.0)));
+        assert_eq!("1266.96 km", format!("{:.2}", Kilometres(1266.9608)));
+        assert_eq!("1852 m", Metres(1852.0).to_string());
+        assert_eq!("1.0 NM", format!("{:.1}", NauticalMiles(1.0)));
+    }
+
+    #[test]
+    fn owned_city() {
+        let dublin: OwnedCity = "Dublin: 53.348°N 6.260°W".parse().unwrap();
+        assert_eq!("Dublin", dublin.name);
+        assert_eq!("Dublin: 53.348°N 6.260°W", dublin.to_string());
+
+        let oslo = OwnedCity::from(City {
+            name: "Oslo",
+            lat: 59.95,
+            lon: 10.75,
+        });
+        assert_eq!("Oslo: 59.950°N 10.750°E", oslo.to_string());
+    }
+
+    const CSV: &str = "name,lat,lon
+Dublin,53.3498,-6.2603
+Zürich,47.3769,8.5417
+Zurich,47.37,8.54
+São Paulo,-23.5505,-46.6333
+San Francisco,37.7749,-122.4194
+San Diego,32.7157,-117.1611
+\"Washington, D.C.\",38.9072,-77.0369
+Santiago,-33.4489,-70.6693
+Broken,north,10
+Nowhere,10
+,10,10
+Far,91,0
+\"Open,1,2
+Suva,-18.1248,178.4501
+Apia,-13.8507,-171.7514
+";
+
+    #[test]
+    fn gazetteer_load_and_lookup() {
+        let (gazetteer, errors) = Gazetteer::from_csv(CSV.as_bytes()).unwrap();
+
+        assert_eq!(10, gazetteer.len());
+        assert_eq!(
+            vec![
+                RowError {
+                    line: 10,
+                    kind: RowErrorKind::Latitude("north".to_owned())
+                },
+                RowError {
+                    line: 11,
+                    kind: RowErrorKind::FieldCount(2)
+                },
+                RowError {
+                    line: 12,
+                    kind: RowErrorKind::EmptyName
+                },
+                RowError {
+                    line: 13,
+                    kind: RowErrorKind::Latitude("91".to_owned())
+                },
+                RowError {
+                    line: 14,
+                    kind: RowErrorKind::UnterminatedQuote
+                },
+            ],
+            errors
+        );
+        assert_eq!(
+            "line 10: latitude `north` is not a number in [-90, 90]",
+            errors[0].to_string()
+        );
+
+        assert_eq!("sao paulo", fold("SÃO PAULO"));
+        assert_eq!("zurich", fold("Zu\u{308}rich"));
+        assert_eq!(2, gazetteer.lookup("ZURICH").len());
+        assert_eq!("São Paulo", gazetteer.lookup("sao paulo")[0].name);
+        assert_eq!(
+            "Washington, D.C.",
+            gazetteer.lookup("washington, d.c.")[0].name
+        );
+        assert!(gazetteer.lookup("Atlantis").is_empty());
+
+        let names =
+            |cities: Vec<&OwnedCity>| cities.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            vec!["San Diego", "San Francisco", "Santiago"],
+            names(gazetteer.autocomplete("SAN", 10))
+        );
+        assert_eq!(vec!["San Diego"], names(gazetteer.autocomplete("san", 1)));
+        assert_eq!(vec!["São Paulo"], names(gazetteer.autocomplete("SÃO", 10)));
+
+        let california = BoundingBox {
+            south: 32.0,
+            west: -125.0,
+            north: 42.0,
+            east: -114.0,
+        };
+        let mut found = names(gazetteer.within(&california));
+        found.sort();
+        assert_eq!(vec!["San Diego", "San Francisco"], found);
+
+        // Across the antimeridian.
+        let pacific = BoundingBox {
+            south: -20.0,
+            west: 170.0,
+            north: -10.0,
+            east: -170.0,
+        };
+        let mut found = names(gazetteer.within(&pacific));
+        found.sort();
+        assert_eq!(vec!["Apia", "Suva"], found);
+    }
+
+    #[test]
+    fn gazetteer_nearest() {
+        // A few thousand points on a grid, checked against a linear scan.
+        let mut cities = Vec::new();
+        for lat in (-85..=85).step_by(5) {
+            for lon in (-180..180).step_by(3) {
+                cities.push(OwnedCity {
+                    name: format!("{lat}/{lon}"),
+                    lat: lat as f32,
+                    lon: lon as f32,
+                });
+            }
+        }
+        let gazetteer = Gazetteer::new(cities.clone());
+        assert!(gazetteer.len() > 4000);
+
+        for target in [
+            LatLon {
+                lat: 53.35,
+                lon: -6.26,
+            },
+            LatLon {
+                lat: -89.0,
+                lon: 0.0,
+            },
+            LatLon {
+                lat: 1.0,
+                lon: 179.9,
+            },
+        ] {
+            let mut expected: Vec<(String, f64)> = cities
+                .iter()
+                .map(|c| (c.name.clone(), target.distance(&c.position()).0))
+                .collect();
+            expected.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+            let found = gazetteer.nearest(&target, 5);
+            assert_eq!(5, found.len());
+            for ((city, distance), (_, reference)) in found.iter().zip(&expected) {
+                assert!(
+                    (distance.0 - reference).abs() < 1e-9,
+                    "{} {}",
+                    city,
+                    distance
+                );
+            }
+        }
+
+        assert!(gazetteer
+            .nearest(&LatLon { lat: 0.0, lon: 0.0 }, 0)
+            .is_empty());
+        assert_eq!(
+            2,
+            Gazetteer::new(cities[..2].to_vec())
+                .nearest(&LatLon { lat: 0.0, lon: 0.0 }, 5)
+                .len()
+        );
+    }
+
+    #[test]
+    fn coordinate_notations() {
+        let dublin = City {
+            name: "Dublin",
+            lat: 53.34778,
+            lon: -6.259722,
+        };
+        let position = LatLon {
+            lat: 53.347778,
+            lon: -6.259722,
+        };
+
+        assert_eq!(
+            dublin.to_string(),
+            dublin.notated(Notation::Decimal).to_string()
+        );
+        assert_eq!(
+            "53°20'52\"N 6°15'35\"W",
+            position.notated(Notation::Dms).to_string()
+        );
+        assert_eq!(
+            "53°20'52.001\"N 6°15'34.999\"W",
+            format!("{:.3}", position.notated(Notation::Dms))
+        );
+        assert_eq!(
+            "53°20.867'N 6°15.583'W",
+            position.notated(Notation::DegreesMinutes).to_string()
+        );
+        assert_eq!(
+            "53.347778, -6.259722",
+            position.notated(Notation::SignedDecimal).to_string()
+        );
+        assert_eq!(
+            "Dublin: 29U 682400 5914460",
+            dublin.notated(Notation::Utm).to_string()
+        );
+
+        // Eiffel Tower, a published UTM reference.
+        let eiffel = LatLon {
+            lat: 48.8583,
+            lon: 2.2945,
+        }
+        .to_utm()
+        .unwrap();
+        assert_eq!((31, 'U'), (eiffel.zone, eiffel.band));
+        assert!((eiffel.easting - 448_251.9).abs() < 1.0);
+        assert!((eiffel.northing - 5_411_943.8).abs() < 1.0);
+
+        // Pasted input in any notation, with loose spacing.
+        for text in [
+            "53.348°N 6.260°W",
+            "53.347778, -6.259722",
+            "53° 20' 52\" N  6° 15' 35\" W",
+            "53°20.867'N 6°15.583'W",
+            "29U 682400 5914460",
+        ] {
+            let parsed: LatLon = text.parse().unwrap();
+            assert!(parsed.distance(&position).0 < 0.05, "{}", text);
+        }
+
+        assert_eq!(
+            Err(ParseError {
+                offset: 4,
+                expected: Expected::Value("a value from 0 to 59")
+            }),
+            Notation::Dms.parse("53°61'0\"N 6°0'0\"W")
+        );
+        assert_eq!(
+            Err(ParseError {
+                offset: 0,
+                expected: Expected::Value("a UTM zone and band like 29U")
+            }),
+            "61U 1 2".parse::<Utm>()
+        );
+    }
+
+    #[test]
+    fn notation_round_trips() {
+        let places = [
+            LatLon {
+                lat: 90.0,
+                lon: 0.0,
+            },
+            LatLon {
+                lat: -90.0,
+                lon: 45.5,
+            },
+            LatLon {
+                lat: 0.0,
+                lon: 180.0,
+            },
+            LatLon {
+                lat: 0.0,
+                lon: -180.0,
+            },
+            LatLon { lat: 0.0, lon: 0.0 },
+            LatLon {
+                lat: -0.000_01,
+                lon: 179.999_99,
+            },
+            LatLon {
+                lat: 59.999_999_9,
+                lon: -12.999_999_9,
+            },
+            LatLon {
+                lat: -33.868_82,
+                lon: 151.209_29,
+            },
+        ];
+
+        // Maximum error in degrees for the default precision of each notation.
+        let tolerance = [
+            (Notation::Decimal, 0.000_5),
+            (Notation::SignedDecimal, 0.000_000_5),
+            (Notation::Dms, 0.5 / 3600.0),
+            (Notation::DegreesMinutes, 0.000_5 / 60.0),
+        ];
+
+        for place in places {
+            for (notation, max_error) in tolerance {
+                let text = place.notated(notation).to_string();
+                let parsed = notation.parse(&text).unwrap();
+                assert!((parsed.lat - place.lat).abs() <= max_error, "{}", text);
+                // 180°E and 180°W are the same meridian.
+                let lon_error = (parsed.lon - place.lon + 540.0).rem_euclid(360.0) - 180.0;
+                assert!(lon_error.abs() <= max_error, "{}", text);
+
+                // Printing the parsed value again gives identical text.
+                assert_eq!(text, parsed.notated(notation).to_string());
+            }
+        }
+
+        assert_eq!(
+            "90°0'0\"N 0°0'0\"E",
+            places[0].notated(Notation::Dms).to_string()
+        );
+        assert_eq!(
+            "0°0'0\"N 180°0'0\"W",
+            places[3].notated(Notation::Dms).to_string()
+        );
+        assert_eq!(
+            "60°0'0\"N 13°0'0\"W",
+            places[6].notated(Notation::Dms).to_string()
+        );
+        assert_eq!(
+            "60°0.000'N 13°0.000'W",
+            places[6].notated(Notation::DegreesMinutes).to_string()
+        );
+
+        // Decimal notation is the `City` default, down to the hemisphere of a
+        // value that rounds to zero.
+        let nearly = City {
+            name: "Nearly",
+            lat: -0.0001,
+            lon: -0.0001,
+        };
+        assert_eq!("Nearly: 0.000°S 0.000°W", nearly.to_string());
+        assert_eq!(
+            nearly.to_string(),
+            nearly.notated(Notation::Decimal).to_string()
+        );
+
+        // UTM stops short of the poles and is sub-millimetre within a zone.
+        assert_eq!(None, places[0].to_utm());
+        assert_eq!(
+            "outside UTM coverage",
+            places[1].notated(Notation::Utm).to_string()
+        );
+        for place in &places[2..] {
+            let utm = place.to_utm().unwrap();
+            let back = utm.to_lat_lon();
+            assert!(back.distance(place).0 < 1e-6, "{}", utm);
+            let reparsed: Utm = format!("{:.6}", utm).parse().unwrap();
+            assert!((reparsed.easting - utm.easting).abs() <= 5e-7);
+            assert!((reparsed.northing - utm.northing).abs() <= 5e-7);
+        }
+        // 180°E is the western edge of zone 1.
+        assert_eq!(1, places[2].to_utm().unwrap().zone);
+    }
+
+    #[test]
+    fn notation_range_errors() {
+        assert_eq!(
+            Err(ParseError {
+                offset: 0,
+                expected: Expected::Value("a latitude within ±90°")
+            }),
+            Notation::Dms.parse("91°0'0\"N 0°0'0\"E")
+        );
+        assert_eq!(
+            Err(ParseError {
+                offset: 6,
+                expected: Expected::Value("a longitude within ±180°")
+            }),
+            Notation::SignedDecimal.parse("10.0, 181")
+        );
+
+        // Both parsers turn away NaN and infinity, hemisphere or not.
+        let nan_latitude = Err(ParseError {
+            offset: 0,
+            expected: Expected::Value("a latitude within ±90°"),
+        });
+        assert_eq!(nan_latitude, Notation::Decimal.parse("NaN°N 0.000°E"));
+        assert_eq!(nan_latitude, "NaN°N 0.000°E".parse::<LatLon>());
+        assert_eq!(nan_latitude, Notation::SignedDecimal.parse("NaN, 0"));
+        assert_eq!(
+            Err(ParseError {
+                offset: 9,
+                expected: Expected::Value("a longitude within ±180°")
+            }),
+            Notation::Decimal.parse("0.000°N inf°W")
+        );
+    }
+
+    #[test]
+    fn geohash_encode_decode() {
+        let jutland = LatLon {
+            lat: 57.64911,
+            lon: 10.40744,
+        };
+        assert_eq!(
+            "u4pruydqqvj",
+            Geohash::encode(jutland, 11).unwrap().as_str()
+        );
+        assert_eq!(
+            Err(GeohashError::Precision(13)),
+            Geohash::encode(jutland, 13)
+        );
+
+        let dublin = City {
+            name: "Dublin",
+            lat: 53.34778,
+            lon: -6.259722,
+        };
+        let hash = dublin.geohash(5).unwrap();
+        assert_eq!("gc7x3", hash.as_str());
+
+        let cell = hash.decode();
+        assert!(cell.bounds.contains(&dublin.position()));
+        assert_eq!(
+            "gc7x3: 53.306°N 6.284°W to 53.350°N 6.240°W",
+            cell.to_string()
+        );
+        assert!((cell.centre().lat - 53.328).abs() < 1e-3);
+
+        let parsed: Geohash = "gc7x3".parse().unwrap();
+        assert_eq!(hash, parsed);
+        assert_eq!(
+            Err(GeohashError::Char {
+                offset: 2,
+                found: 'a'
+            }),
+            "gcax3".parse::<Geohash>()
+        );
+        assert_eq!(Err(GeohashError::Precision(0)), "".parse::<Geohash>());
+
+        assert_eq!(Some("gc7x".parse().unwrap()), hash.parent());
+        assert!(hash.parent().unwrap().contains(&hash));
+        assert!(!hash.contains(&hash.parent().unwrap()));
+    }
+
+    #[test]
+    fn geohash_neighbors() {
+        let hash: Geohash = "gbsuv".parse().unwrap();
+        let expected = [
+            "gbsvj", "gbsvn", "gbsuy", "gbsuw", "gbsut", "gbsus", "gbsuu", "gbsvh",
+        ];
+        for ((direction, neighbor), want) in hash.neighbors().into_iter().zip(expected) {
+            assert_eq!(want, neighbor.as_str(), "{:?}", direction);
+        }
+
+        // Across the antimeridian and at the pole.
+        let east_edge = Geohash::encode(
+            LatLon {
+                lat: 0.1,
+                lon: 179.99,
+            },
+            3,
+        )
+        .unwrap();
+        let wrapped = east_edge.neighbor(Direction::East).unwrap();
+        assert!(wrapped.decode().bounds.west == -180.0);
+        let top = Geohash::encode(
+            LatLon {
+                lat: 89.99,
+                lon: 0.0,
+            },
+            4,
+        )
+        .unwrap();
+        assert_eq!(None, top.neighbor(Direction::North));
+        assert_eq!(5, top.neighbors().len());
+    }
+
+    #[test]
+    fn geohash_grouping() {
+        let cities = [
+            City {
+                name: "Dublin",
+                lat: 53.34778,
+                lon: -6.259722,
+            },
+            City {
+                name: "Dún Laoghaire",
+                lat: 53.2945,
+                lon: -6.1339,
+            },
+            City {
+                name: "Oslo",
+                lat: 59.95,
+                lon: 10.75,
+            },
+            City {
+                name: "Vancouver",
+                lat: 49.25,
+                lon: -123.1,
+            },
+        ];
+        let groups = group_by_cell(&cities, 2, |c| c.position()).unwrap();
+
+        let summary: Vec<(String, Vec<&str>)> = groups
+            .iter()
+            .map(|(hash, cities)| (hash.to_string(), cities.iter().map(|c| c.name).collect()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("c2".to_owned(), vec!["Vancouver"]),
+                ("gc".to_owned(), vec!["Dublin", "Dún Laoghaire"]),
+                ("u4".to_owned(), vec!["Oslo"]),
+            ],
+            summary
+        );
+    }
+
+    #[test]
+    fn list_styles() {
+        let list = List(vec![1, 2, 3]);
+        assert_eq!("[0: 1, 1: 2, 2: 3]", list.to_string());
+
+        let style = ListStyle::new()
+            .separator("; ")
+            .brackets("{", "}")
+            .index_base(1);
+        assert_eq!("{1: 1; 2: 2; 3: 3}", list.styled(&style).to_string());
+
+        let style = ListStyle::new().indices(false).brackets("<", ">");
+        assert_eq!("<1, 2, 3>", list.styled(&style).to_string());
+
+        let long = List((1..=1000).collect());
+        let style = ListStyle::new().max_items(2);
+        assert_eq!(
+            "[0: 1, 1: 2, ... 998 more]",
+            long.styled(&style).to_string()
+        );
+        assert_eq!(
+            "[... 3 more]",
+            list.styled(&ListStyle::new().max_items(0)).to_string()
+        );
+        assert_eq!(
+            "[0: 1, 1: 2, 2: 3]",
+            list.styled(&ListStyle::new().max_items(5)).to_string()
+        );
+
+        let words = List(vec!["a", "b"]);
+        assert_eq!("[0: a, 1: b]", words.to_string());
+        assert_eq!("[]", List::<i32>(vec![]).to_string());
+        assert_eq!("[]", format!("{:#}", List::<i32>(vec![])));
+    }
+
+    #[test]
+    fn list_item_flags() {
+        let floats = List(vec![1.0, 2.5, -3.25]);
+        assert_eq!("[0: 1.00, 1: 2.50, 2: -3.25]", format!("{:.2}", floats));
+        assert_eq!("[0: +1, 1: +2.5, 2: -3.25]", format!("{:+}", floats));
+        assert_eq!("[0:   1.0, 1:   2.5, 2:  -3.2]", format!("{:>5.1}", floats));
+
+        assert_eq!(
+            "[\n    0: 1,\n    1: 2,\n    2: 3\n]",
+            format!("{:#}", List(vec![1, 2, 3]))
+        );
+        let style = ListStyle::new().max_items(1).index_base(1);
+        assert_eq!(
+            "[\n    1: 1.0,\n    ... 2 more\n]",
+            format!("{:#.1}", floats.styled(&style))
+        );
+        assert_eq!(
+            "[\n    ... 3 more\n]",
+            format!("{:#}", floats.styled(&ListStyle::new().max_items(0)))
+        );
+    }
+
+    #[test]
+    fn nested_lists() {
+        let nested = List(vec![List(vec![1, 2]), List(vec![3])]);
+        assert_eq!("[0: [0: 1, 1: 2], 1: [0: 3]]", nested.to_string());
+        assert_eq!(
+            "[
+    0: [
+        0: 1,
+        1: 2
+    ],
+    1: [
+        0: 3
+    ]
+]",
+            format!("{:#}", nested)
+        );
+
+        let grid = List(vec![List(vec![0.5, 0.25])]);
+        assert_eq!("[0: [0: 0.5, 1: 0.2]]", format!("{:.1}", grid));
+    }
+
+    #[test]
+    fn table_styles() {
+        let cities = [
+            City {
+                name: "Dublin",
+                lat: 53.34778,
+                lon: -6.259722,
+            },
+            City {
+                name: "Zürich",
+                lat: 47.3769,
+                lon: 8.5417,
+            },
+            City {
+                name: "東京",
+                lat: 35.6762,
+                lon: 139.6503,
+            },
+        ];
+        let table = Table::from_rows(&cities);
+
+        assert_eq!(
+            "\
++--------+----------+-----------+
+| City   | Latitude | Longitude |
++--------+----------+-----------+
+| Dublin |   53.348 |    -6.260 |
+| Zürich |   47.377 |     8.542 |
+| 東京   |   35.676 |   139.650 |
++--------+----------+-----------+
+",
+            table.render(Style::Ascii)
+        );
+        assert_eq!(table.render(Style::Ascii), table.to_string());
+
+        assert_eq!(
+            "\
+┌────────┬──────────┬───────────┐
+│ City   │ Latitude │ Longitude │
+├────────┼──────────┼───────────┤
+│ Dublin │   53.348 │    -6.260 │
+│ Zürich │   47.377 │     8.542 │
+│ 東京   │   35.676 │   139.650 │
+└────────┴──────────┴───────────┘
+",
+            format!("{:#}", table)
+        );
+
+        assert_eq!(
+            "\
+| City   | Latitude | Longitude |
+| :----- | -------: | --------: |
+| Dublin |   53.348 |    -6.260 |
+| Zürich |   47.377 |     8.542 |
+| 東京   |   35.676 |   139.650 |
+",
+            table.render(Style::Markdown)
+        );
+
+        assert_eq!(
+            "City,Latitude,Longitude\r\nDublin,53.348,-6.260\r\nZürich,47.377,8.542\r\n東京,35.676,139.650\r\n",
+            table.render(Style::Csv)
+        );
+    }
+
+    #[test]
+    fn table_columns() {
+        let mut table = Table::new(vec![
+            Column::new("Name").max_width(6),
+            Column::new("Id").fill('0').align(Align::Right),
+            Column::new("Tag").align(Align::Centre),
+        ]);
+        table
+            .push(vec!["Llanfairpwllgwyngyll".into(), 7.into(), "a|b".into()])
+            .unwrap();
+        table
+            .push(vec!["Bo".into(), 42.into(), Cell::Empty])
+            .unwrap();
+        assert_eq!(
+            Err(RowLengthError {
+                expected: 3,
+                found: 1
+            }),
+            table.push(vec![1.into()])
+        );
+        assert_eq!(2, table.len());
+
+        // Headers are padded with spaces, data cells with the column's fill.
+        assert_eq!(
+            "\
++--------+----+-----+
+| Name   | Id | Tag |
++--------+----+-----+
+| Lla... | 07 | a|b |
+| Bo     | 42 |     |
++--------+----+-----+
+",
+            table.render(Style::Ascii)
+        );
+        assert_eq!(
+            "\
+| Name   |  Id | Tag  |
+| :----- | --: | :--: |
+| Llanf… | 007 | a\\|b |
+| Bo     | 042 |      |
+",
+            table.render(Style::Markdown)
+        );
+        assert_eq!(
+            "Name,Id,Tag\r\nLlanfairpwllgwyngyll,7,a|b\r\nBo,42,\r\n",
+            table.render(Style::Csv)
+        );
+
+        let mut quoted = Table::new(vec![Column::new("Quote")]);
+        quoted.push(vec!["say \"hi\", then\nleave".into()]).unwrap();
+        assert_eq!(
+            "Quote\r\n\"say \"\"hi\"\", then\nleave\"\r\n",
+            quoted.render(Style::Csv)
+        );
+    }
+
+    #[test]
+    fn table_rows_and_widths() {
+        let ranges = [MinMax::new(-300, 300).unwrap(), MinMax::new(0, 14).unwrap()];
+        assert_eq!(
+            "\
++------+-----+
+|  Min | Max |
++------+-----+
+| -300 | 300 |
+|    0 |  14 |
++------+-----+
+",
+            Table::from_rows(&ranges).to_string()
+        );
+
+        let mut points = Table::from_rows(&[Point2D::new(3.3, -7.25)]);
+        *points.column_mut(1).unwrap() = Column::new("y").precision(1);
+        assert_eq!("x,y\r\n3.3,-7.2\r\n", points.render(Style::Csv));
+
+        assert_eq!(6, display_width("Zürich"));
+        assert_eq!(6, display_width("Zu\u{308}rich"));
+        assert_eq!(4, display_width("東京"));
+        assert_eq!(8, display_width("서울특별"));
+        assert_eq!(0, display_width(""));
+    }
+
+    #[test]
+    fn complex_display() {
+        let z = Complex::new(3.3, 7.2);
+        assert_eq!("3.3+7.2i", z.to_string());
+        assert_eq!("Complex { real: 3.3, imag: 7.2 }", format!("{:?}", z));
+        assert_eq!("3.3-7.2i", z.conj().to_string());
+        assert_eq!("-3.300-7.200i", format!("{:.3}", -z));
+        assert_eq!(
+            "+3+0i",
+            format!("{:+.0}", z.conj() - Complex::new(0.3, -7.2))
+        );
+        assert_eq!("[  1-2i  ]", format!("[{:^8}]", Complex::new(1.0, -2.0)));
+        assert_eq!("0-0i", Complex::new(0.0, -0.0).to_string());
+        assert_eq!(
+            "NaN+infi",
+            Complex::new(f64::NAN, f64::INFINITY).to_string()
+        );
+    }
+
+    #[test]
+    fn complex_arithmetic() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(3.0, -4.0);
+        assert_eq!(Complex::new(4.0, -2.0), a + b);
+        assert_eq!(Complex::new(-2.0, 6.0), a - b);
+        assert_eq!(Complex::new(11.0, 2.0), a * b);
+        assert_eq!(Complex::new(-0.2, 0.4), a / b);
+        assert_eq!(Complex::new(-1.0, 0.0), Complex::I * Complex::I);
+        assert_eq!(Complex::new(2.0, 2.0), a + 1.0);
+        assert_eq!(Complex::new(1.0, -2.0), 2.0 - a);
+        assert_eq!(Complex::new(0.2, -0.4), 1.0 / a);
+
+        let mut c = a;
+        c += b;
+        c *= 2.0;
+        c -= Complex::I;
+        c /= Complex::new(0.0, 1.0);
+        assert_eq!(Complex::new(-5.0, -8.0), c);
+
+        // Smith's division doesn't overflow on huge divisors.
+        let huge = Complex::new(1e300, 1e300);
+        assert!((huge / huge).approx_eq(Complex::ONE, 1e-12));
+
+        assert_eq!(5.0, b.modulus());
+        assert_eq!(std::f64::consts::FRAC_PI_2, Complex::I.argument());
+        let (r, theta) = b.to_polar();
+        assert!(Complex::from_polar(r, theta).approx_eq(b, 1e-12));
+    }
+
+    #[test]
+    fn complex_functions() {
+        use std::f64::consts::PI;
+
+        // Euler's identity.
+        let euler = (Complex::I * PI).exp() + 1.0;
+        assert!(euler.approx_eq(Complex::ZERO, 1e-12));
+        assert!(Complex::new(-1.0, 0.0)
+            .ln()
+            .approx_eq(Complex::new(0.0, PI), 1e-12));
+        let z = Complex::new(0.5, -1.25);
+        assert!(z.ln().exp().approx_eq(z, 1e-12));
+
+        assert!(Complex::new(-4.0, 0.0)
+            .powf(0.5)
+            .approx_eq(Complex::new(0.0, 2.0), 1e-12));
+        assert!(Complex::I
+            .powf(2.0)
+            .approx_eq(Complex::new(-1.0, 0.0), 1e-12));
+        assert!(z.powf(3.0).approx_eq(z * z * z, 1e-12));
+        assert_eq!(Complex::ONE, Complex::ZERO.powf(0.0));
+        assert_eq!(Complex::ZERO, Complex::ZERO.powf(2.0));
+        assert_eq!(Complex::new(f64::INFINITY, 0.0), Complex::ZERO.powf(-1.0));
+    }
+
+    #[test]
+    fn complex_parse() {
+        let cases = [
+            ("3.3+7.2i", Complex::new(3.3, 7.2)),
+            ("-1.5-2i", Complex::new(-1.5, -2.0)),
+            ("i", Complex::I),
+            ("-i", Complex::new(0.0, -1.0)),
+            ("4", Complex::new(4.0, 0.0)),
+            ("+4", Complex::new(4.0, 0.0)),
+            ("2.5e-3i", Complex::new(0.0, 0.0025)),
+            ("1E2-i", Complex::new(100.0, -1.0)),
+            ("-inf+0i", Complex::new(f64::NEG_INFINITY, 0.0)),
+            (".5+.5i", Complex::new(0.5, 0.5)),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(Ok(expected), text.parse::<Complex>(), "{}", text);
+        }
+        round_trip::<Complex>("-0.1+0.00000000000000000001i");
+        round_trip::<Complex>("NaN-infi");
+
+        let error = |offset, expected| Err(ParseError { offset, expected });
+        assert_eq!(error(0, Expected::Number), "".parse::<Complex>());
+        assert_eq!(error(2, Expected::Text("i")), "4+".parse::<Complex>());
+        assert_eq!(
+            error(1, Expected::OneOf(&["+", "-", "i"])),
+            "4j".parse::<Complex>()
+        );
+        assert_eq!(error(4, Expected::End), "1+2i ".parse::<Complex>());
+        assert_eq!(error(1, Expected::Number), "-".parse::<Complex>());
+    }
+
+    #[test]
+    fn rgb_formats() {
+        let color = Rgb::new(128, 255, 90);
+        assert_eq!("RGB (128, 255, 90) 0x80FF5A", color.to_string());
+        assert_eq!("80FF5A", format!("{:X}", color));
+        assert_eq!("0x80ff5a", format!("{:#x}", color));
+        assert_eq!("000000", format!("{:x}", Rgb::default()));
+        assert_eq!("RGB (0, 3, 254) 0x0003FE", Rgb::new(0, 3, 254).to_string());
+        assert_eq!(
+            "[RGB (0, 0, 0) 0x000000   ]",
+            format!("[{:<25}]", Rgb::default())
+        );
+
+        assert_eq!(Ok(color), "#80ff5a".parse());
+        assert_eq!(Ok(color), "#80FF5A".parse());
+        assert_eq!(Ok(Rgb::new(0x88, 0xff, 0x55)), "#8f5".parse());
+        assert_eq!(Ok(color), "rgb(128, 255, 90)".parse());
+        assert_eq!(Ok(color), "rgb( 128 ,255,90 )".parse());
+        assert_eq!(Ok(Rgb::new(102, 51, 153)), "RebeccaPurple".parse());
+
+        let error = |offset, expected| Err(ParseError { offset, expected });
+        assert_eq!(
+            error(1, Expected::Value("3 or 6 hex digits")),
+            "#80ff5".parse::<Rgb>()
+        );
+        assert_eq!(error(4, Expected::End), "#8f5 ".parse::<Rgb>());
+        assert_eq!(
+            error(9, Expected::Value("a channel from 0 to 255")),
+            "rgb(128, 256, 90)".parse::<Rgb>()
+        );
+        assert_eq!(
+            error(12, Expected::Text(",")),
+            "rgb(128, 255)".parse::<Rgb>()
+        );
+        assert_eq!(
+            error(0, Expected::OneOf(&["#", "rgb(", "a CSS colour name"])),
+            "bluish".parse::<Rgb>()
+        );
+    }
+
+    #[test]
+    fn rgb_conversions() {
+        let color = Rgb::new(128, 255, 90);
+        let hsl = Hsl::from(color);
+        assert_eq!("hsl(106, 100%, 68%)", hsl.to_string());
+        assert_eq!("hsl(106.2, 100.0%, 67.6%)", format!("{:.1}", hsl));
+        assert_eq!("hsv(106, 65%, 100%)", Hsv::from(color).to_string());
+
+        assert_eq!(
+            Rgb::new(255, 0, 0),
+            Rgb::from(Hsl {
+                hue: 360.0,
+                saturation: 1.0,
+                lightness: 0.5
+            })
+        );
+        assert_eq!(
+            Rgb::new(0, 0, 255),
+            Rgb::from(Hsv {
+                hue: -120.0,
+                saturation: 1.0,
+                value: 1.0
+            })
+        );
+        assert_eq!(
+            Rgb::new(255, 255, 255),
+            Rgb::from(Hsl {
+                hue: 10.0,
+                saturation: 2.0,
+                lightness: 1.5
+            })
+        );
+        assert_eq!(0.0, Hsl::from(Rgb::new(77, 77, 77)).saturation);
+
+        // Every channel value survives the round trip through both models.
+        for v in (0..=255).step_by(5) {
+            for rgb in [
+                Rgb::new(v, 255 - v, 3),
+                Rgb::new(v / 2, v, v / 3),
+                Rgb::new(v, v, v),
+            ] {
+                assert_eq!(rgb, Rgb::from(Hsl::from(rgb)));
+                assert_eq!(rgb, Rgb::from(Hsv::from(rgb)));
+            }
+        }
+
+        assert_eq!(Some(Rgb::new(255, 165, 0)), Rgb::named("orange"));
+        assert_eq!(None, Rgb::named("bluish"));
+        assert_eq!(Some("gray"), Rgb::new(128, 128, 128).name());
+        assert_eq!(Some("aqua"), Rgb::named("cyan").unwrap().name());
+        assert_eq!(None, color.name());
+    }
+
+    #[test]
+    fn rgb_blend_and_contrast() {
+        let black = Rgb::named("black").unwrap();
+        let white = Rgb::named("white").unwrap();
+        assert_eq!(Rgb::new(128, 128, 128), black.blend(white, 0.5));
+        assert_eq!(black, black.blend(white, -1.0));
+        assert_eq!(white, black.blend(white, 7.0));
+        assert_eq!(
+            Rgb::new(191, 64, 0),
+            Rgb::new(255, 0, 0).blend(Rgb::new(0, 255, 0), 0.25)
+        );
+
+        assert_eq!(21.0, black.contrast_ratio(white));
+        assert_eq!(21.0, white.contrast_ratio(black));
+        assert_eq!(1.0, white.contrast_ratio(white));
+        // The WCAG example: #767676 on white just passes AA at 4.5:1.
+        let ratio = Rgb::new(0x76, 0x76, 0x76).contrast_ratio(white);
+        assert!((ratio - 4.54).abs() < 0.01);
+    }
+
+    #[test]
+    fn rgb_ansi() {
+        let color = Rgb::new(128, 255, 90);
+        assert_eq!(
+            "\x1b[38;2;128;255;90m",
+            color.ansi_fg(ColorDepth::TrueColor)
+        );
+        assert_eq!(
+            "\x1b[48;2;128;255;90m",
+            color.ansi_bg(ColorDepth::TrueColor)
+        );
+        assert_eq!("\x1b[38;5;119m", color.ansi_fg(ColorDepth::Ansi256));
+        assert_eq!(
+            "\x1b[38;5;196mhot\x1b[0m",
+            Rgb::new(255, 0, 0).paint("hot", ColorDepth::Ansi256)
+        );
+
+        assert_eq!(16, Rgb::new(0, 0, 0).to_ansi256());
+        assert_eq!(231, Rgb::new(255, 255, 255).to_ansi256());
+        assert_eq!(21, Rgb::new(0, 0, 255).to_ansi256());
+        // Greys land on the finer grey ramp rather than the cube.
+        assert_eq!(244, Rgb::new(128, 128, 128).to_ansi256());
+        assert_eq!(232, Rgb::new(8, 8, 8).to_ansi256());
+    }
+
+    #[test]
+    fn grouped_numbers() {
+        assert_eq!(
+            "1,234,567.8",
+            Grouped::new(1234567.8, Locale::EN).to_string()
+        );
+        assert_eq!(
+            "1.234.567,8",
+            Grouped::new(1234567.8, Locale::DE).to_string()
+        );
+        assert_eq!(
+            "1\u{202f}234\u{202f}567,80",
+            format!("{:.2}", Grouped::new(1234567.8, Locale::FR))
+        );
+        assert_eq!("-12'345", Grouped::new(-12345, Locale::CH).to_string());
+        assert_eq!("999", Grouped::new(999u16, Locale::EN).to_string());
+        assert_eq!(
+            "-100,000",
+            Grouped::new(-100_000i64, Locale::EN).to_string()
+        );
+        assert_eq!("0.5", Grouped::new(0.5, Locale::EN).to_string());
+        assert_eq!("inf", Grouped::new(f64::INFINITY, Locale::EN).to_string());
+
+        // Right-aligned by default, like the numbers in `formart_print`.
+        assert_eq!("   1,234", format!("{:8}", Grouped::new(1234, Locale::EN)));
+        assert_eq!(
+            "1,234___",
+            format!("{:_<8}", Grouped::new(1234, Locale::EN))
+        );
+        assert_eq!(
+            "**1,234.0**",
+            format!("{:*^11.1}", Grouped::new(1234.0, Locale::EN))
+        );
+    }
+
+    #[test]
+    fn prefixed_numbers() {
+        assert_eq!("1.2 k", SiPrefix::new(1234.0).to_string());
+        assert_eq!("1.23 km", format!("{:.2}", SiPrefix::new(1234.0).unit("m")));
+        assert_eq!("4.7 µF", SiPrefix::new(4.7e-6).unit("F").to_string());
+        assert_eq!("-3.0 MW", SiPrefix::new(-3e6).unit("W").to_string());
+        assert_eq!("12", SiPrefix::new(12.0).to_string());
+        assert_eq!("12.5 g", SiPrefix::new(12.5).unit("g").to_string());
+        assert_eq!("1.0 k", SiPrefix::new(999.96).to_string());
+        assert_eq!("0 V", SiPrefix::new(0.0).unit("V").to_string());
+        assert_eq!("1000.0 Q", SiPrefix::new(1e33).to_string());
+
+        assert_eq!("3.4 MiB", BinaryPrefix::bytes(3_565_158).to_string());
+        assert_eq!("512 B", BinaryPrefix::bytes(512).to_string());
+        assert_eq!("1.0 KiB", BinaryPrefix::bytes(1024).to_string());
+        assert_eq!("16.00 EiB", format!("{:.2}", BinaryPrefix::bytes(u64::MAX)));
+        assert_eq!("2.0 Ki", BinaryPrefix::new(2048.0).to_string());
+        assert_eq!(
+            "|  3.4 MiB|",
+            format!("|{:>9}|", BinaryPrefix::bytes(3_565_158))
+        );
+    }
+
+    #[test]
+    fn ordinals() {
+        let ordinals: Vec<String> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 23, 101, 111, 112, 1000]
+            .iter()
+            .map(|&n| Ordinal(n).to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "23rd",
+                "101st", "111th", "112th", "1000th"
+            ],
+            ordinals
+        );
+        assert_eq!("0th", Ordinal(0).to_string());
+        assert_eq!("-1st", Ordinal(-1).to_string());
+        assert_eq!("  22nd", format!("{:6}", Ordinal(22)));
+    }
+
+    #[test]
+    fn roman_numerals() {
+        let cases = [
+            (1, "I"),
+            (4, "IV"),
+            (9, "IX"),
+            (14, "XIV"),
+            (40, "XL"),
+            (90, "XC"),
+            (400, "CD"),
+            (1994, "MCMXCIV"),
+            (2024, "MMXXIV"),
+            (3999, "MMMCMXCIX"),
+        ];
+        for (value, text) in cases {
+            let roman = Roman::new(value).unwrap();
+            assert_eq!(text, roman.to_string());
+            assert_eq!(Ok(roman), text.parse());
+            assert_eq!(Ok(roman), text.to_lowercase().parse());
+        }
+        assert_eq!("mcmxciv", format!("{:#}", Roman::new(1994).unwrap()));
+        assert_eq!("   XIV", format!("{:6}", Roman::new(14).unwrap()));
+        assert_eq!(None, Roman::new(0));
+        assert_eq!(None, Roman::new(4000));
+
+        // Every value survives the round trip.
+        for value in 1..=Roman::MAX {
+            let roman = Roman::new(value).unwrap();
+            assert_eq!(Ok(roman), roman.to_string().parse());
+        }
+
+        let not_standard = |offset| {
+            Err(ParseError {
+                offset,
+                expected: Expected::Value("a Roman numeral from I to MMMCMXCIX in standard form"),
+            })
+        };
+        assert_eq!(not_standard(1), "IIII".parse::<Roman>());
+        assert_eq!(not_standard(0), "IC".parse::<Roman>());
+        assert_eq!(not_standard(0), "VV".parse::<Roman>());
+        assert_eq!(not_standard(3), "MMMM".parse::<Roman>());
+        let digits = Expected::OneOf(&["I", "V", "X", "L", "C", "D", "M"]);
+        assert_eq!(
+            Err(ParseError {
+                offset: 2,
+                expected: digits
+            }),
+            "XIZ".parse::<Roman>()
+        );
+        assert_eq!(
+            Err(ParseError {
+                offset: 0,
+                expected: digits
+            }),
+            "".parse::<Roman>()
+        );
+    }
+
+    #[test]
+    fn radix_numbers() {
+        assert_eq!(
+            "10000111100101100",
+            Radix::new(69420, 2).unwrap().to_string()
+        );
+        assert_eq!("207454", Radix::new(69420, 8).unwrap().to_string());
+        assert_eq!("10f2c", Radix::new(69420, 16).unwrap().to_string());
+        assert_eq!(
+            "0x10F2C",
+            Radix::new(69420, 16)
+                .unwrap()
+                .prefix(true)
+                .uppercase(true)
+                .to_string()
+        );
+        assert_eq!(
+            "0b1_0000_1111_0010_1100",
+            format!("{:#}", Radix::new(69420, 2).unwrap().group(4, '_'))
+        );
+        assert_eq!(
+            "dead_beef",
+            Radix::new(0xdead_beef_u32, 16)
+                .unwrap()
+                .group(4, '_')
+                .to_string()
+        );
+        assert_eq!("-0o17", format!("{:#}", Radix::new(-15, 8).unwrap()));
+        assert_eq!("1hkc", Radix::new(69420, 36).unwrap().to_string());
+        assert_eq!("0", Radix::new(0, 7).unwrap().to_string());
+        assert_eq!("10", Radix::new(3, 3).unwrap().prefix(true).to_string());
+        assert_eq!(
+            format!("-{:b}", i128::MIN.unsigned_abs()),
+            Radix::new(i128::MIN, 2).unwrap().to_string()
+        );
+        assert_eq!(
+            "0000010f2c",
+            format!("{:0>10}", Radix::new(69420, 16).unwrap())
+        );
+        assert_eq!(None, Radix::new(1, 1));
+        assert_eq!(None, Radix::new(1, 37));
+    }
+
+    #[test]
+    fn templates() {
+        let render = |template: &str, args: &[Value]| {
+            Template::parse(template).unwrap().render(args).unwrap()
+        };
+        let named = |template: &str, args: &[(&str, Value)]| {
+            Template::parse(template).unwrap().render(args).unwrap()
+        };
+
+        // The same outputs as `formart_print`, but from strings.
+        assert_eq!("31 days", render("{} days", &[31.into()]));
+        assert_eq!(
+            "Alice, this is Bob. Bob, this is Alice",
+            render(
+                "{0}, this is {1}. {1}, this is {0}",
+                &["Alice".into(), "Bob".into()]
+            )
+        );
+        assert_eq!(
+            "Base 2 (binary): 10000111100101100",
+            render("Base 2 (binary): {:b}", &[69420.into()])
+        );
+        assert_eq!(
+            "207454 10f2c 10F2C",
+            render("{0:o} {0:x} {0:X}", &[69420.into()])
+        );
+        assert_eq!("    1", named("{number:>5}", &[("number", 1.into())]));
+        assert_eq!("00001", named("{number:0>5}", &[("number", 1.into())]));
+        assert_eq!(
+            "00001",
+            named(
+                "{number:0>width$}",
+                &[("number", 1.into()), ("width", 5.into())]
+            )
+        );
+        assert_eq!("2.346", named("{value:.3}", &[("value", 2.34567.into())]));
+
+        // Everything else the spec can say, checked against `format!` itself.
+        let cases: [(&str, Value, String); 15] = [
+            ("{:+}", 5.into(), format!("{:+}", 5)),
+            ("{:#x}", 255.into(), format!("{:#x}", 255)),
+            ("{:#010b}", 5.into(), format!("{:#010b}", 5)),
+            ("{:08.2}", (-7.25678).into(), format!("{:08.2}", -7.25678)),
+            ("{:x}", (-1_i64).into(), format!("{:x}", -1_i64)),
+            ("{:x}", (-1).into(), format!("{:x}", -1)),
+            ("{:#b}", (-2_i8).into(), format!("{:#b}", -2_i8)),
+            ("{:o}", (-8_i16).into(), format!("{:o}", -8_i16)),
+            ("{:*^9}", "mid".into(), format!("{:*^9}", "mid")),
+            ("{:.2}", "truncate".into(), format!("{:.2}", "truncate")),
+            ("{:?}", "quote".into(), format!("{:?}", "quote")),
+            ("{:?}", 1.0.into(), format!("{:?}", 1.0)),
+            ("{:.2e}", 1234.into(), format!("{:.2e}", 1234)),
+            ("{:E}", 0.00012.into(), format!("{:E}", 0.00012)),
+            ("{}", 0.1_f32.into(), format!("{}", 0.1_f32)),
+        ];
+        for (template, value, expected) in cases {
+            assert_eq!(expected, render(template, &[value]), "{}", template);
+        }
+        assert_eq!("{1.50}", render("{{{:.*}}}", &[2.into(), 1.5.into()]));
+    }
+
+    #[test]
+    fn templates_against_records() {
+        let dublin = City {
+            name: "Dublin",
+            lat: 53.34778,
+            lon: -6.25972,
+        };
+        let coordinates: Template = "{name}: {lat:.3}°{ns} {lon:.3}°{ew}".parse().unwrap();
+        assert_eq!(dublin.to_string(), coordinates.render(&dublin).unwrap());
+        assert_eq!(
+            "-6.26",
+            Template::parse("{longitude:.2}")
+                .unwrap()
+                .render(&dublin)
+                .unwrap()
+        );
+
+        let range = MinMax::new(-300, 300).unwrap();
+        assert_eq!(
+            "[-300, 300] [-300, 300]",
+            Template::parse("[{min}, {max}] [{}, {}]")
+                .unwrap()
+                .render(&range)
+                .unwrap()
+        );
+        assert_eq!(
+            "(   3.3, -7.25)",
+            Template::parse("({x:6}, {y})")
+                .unwrap()
+                .render(&Point2D::new(3.3, -7.25))
+                .unwrap()
+        );
+
+        let phone =
+            Template::parse("({job.phone_number.area_code:>3}) {job.phone_number.number}").unwrap();
+        let maybe_code = |area_code| Person {
+            job: Some(Job {
+                phone_number: Some(PhoneNumber {
+                    area_code,
+                    number: 439222222,
+                }),
+            }),
+        };
+        assert_eq!(
+            "( 61) 439222222",
+            phone.render(&maybe_code(Some(61))).unwrap()
+        );
+        assert_eq!("(   ) 439222222", phone.render(&maybe_code(None)).unwrap());
+        assert_eq!(
+            "None",
+            Template::parse("{job.phone_number.area_code:?}")
+                .unwrap()
+                .render(&Person { job: None })
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn template_errors() {
+        fn error<T>(column: usize, kind: TemplateErrorKind) -> Result<T, TemplateError> {
+            Err(TemplateError { column, kind })
+        }
+
+        assert_eq!(
+            error(7, TemplateErrorKind::Unclosed),
+            Template::parse("{name:")
+        );
+        assert_eq!(
+            error(8, TemplateErrorKind::UnmatchedClose),
+            Template::parse("a {} b }")
+        );
+        assert_eq!(
+            error(10, TemplateErrorKind::Unexpected('z')),
+            Template::parse("{} {name:z}")
+        );
+        assert_eq!(
+            error(6, TemplateErrorKind::Unexpected('x')),
+            Template::parse("{:>8.x}")
+        );
+        assert_eq!(
+            "column 6: unexpected `x`",
+            Template::parse("{:>8.x}").unwrap_err().to_string()
+        );
+
+        let dublin = City {
+            name: "Dublin",
+            lat: 53.34778,
+            lon: -6.25972,
+        };
+        let render = |template: &str| Template::parse(template).unwrap().render(&dublin);
+        assert_eq!(
+            error(8, TemplateErrorKind::UnknownField("height".to_owned())),
+            render("{name} {height}")
+        );
+        assert_eq!(
+            error(1, TemplateErrorKind::MissingArgument(0)),
+            render("{}")
+        );
+        assert_eq!(
+            error(1, TemplateErrorKind::NotACount),
+            render("{lat:name$}")
+        );
+        assert_eq!(
+            error(1, TemplateErrorKind::Unsupported('x')),
+            render("{name:x}")
+        );
+    }
+
+    #[test]
+    fn redacted_debug() {
+        let dublin = City {
+            name: "Dublin",
+            lat: 53.34778,
+            lon: -6.25972,
+        };
+        assert_eq!(format!("{:?}", dublin), format!("{:?}", dublin.redacted()));
+        assert_eq!(
+            format!("{:#?}", dublin),
+            format!("{:#?}", dublin.redacted())
+        );
+        assert_eq!(
+            "City { lat: 53.34778, lon: -6.25972, name: \"Dublin\" }",
+            format!("{:?}", DebugOptions::new().sort_keys(true).show(&dublin))
+        );
+        assert_eq!(
+            "City { name: \"Dublin\", lat: \"***\", lon: \"***\" }",
+            format!(
+                "{:?}",
+                DebugOptions::new()
+                    .redact("lat")
+                    .redact("lon")
+                    .show(&dublin)
+            )
+        );
+        assert_eq!(
+            "City { .. }",
+            format!("{:?}", DebugOptions::new().max_depth(0).show(&dublin))
+        );
+
+        // Any type can take part by describing itself.
+        struct Login {
+            user: String,
+            password: String,
+            last_city: Option<City>,
+        }
+
+        impl Inspect for Login {
+            fn inspect(&self) -> Node {
+                Node::record("Login")
+                    .field("user", &self.user)
+                    .secret_field("password", &self.password)
+                    .field("last_city", &self.last_city)
+                    .finish()
+            }
+        }
+
+        let login = Login {
+            user: "zonin".to_owned(),
+            password: "hunter2".to_owned(),
+            last_city: Some(dublin),
+        };
+        assert_eq!(
+            "Login { user: \"zonin\", password: \"***\", last_city: Some(City { .. }) }",
+            format!("{:?}", DebugOptions::new().max_depth(1).show(&login))
+        );
+        // A redacted `Option` doesn't give away whether it was set.
+        assert_eq!(
+            "Login { user: \"zonin\", password: \"***\", last_city: \"***\" }",
+            format!("{:?}", DebugOptions::new().redact("last_city").show(&login))
+        );
+        let logged_out = Login {
+            password: "pw".to_owned(),
+            last_city: None,
+            ..login
+        };
+        assert_eq!(
+            "Login {\n    user: \"zonin\",\n    password: \"***\",\n    last_city: None,\n}",
+            format!("{:#?}", logged_out.redacted())
+        );
+        assert_eq!(
+            "Login { user: \"zonin\", password: \"***\", last_city: \"***\" }",
+            format!(
+                "{:?}",
+                DebugOptions::new()
+                    .hide_none(true)
+                    .redact("last_city")
+                    .show(&logged_out)
+            )
+        );
     }
 }
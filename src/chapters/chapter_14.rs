@@ -84,6 +84,361 @@ pub mod generics {
         }
     }
 
+    // Shapes made of real coordinates. A `Polygon` gets its area from the
+    // shoelace formula, so it can go anywhere a `HasArea` bound is asked for.
+    #[allow(dead_code)]
+    pub mod shapes {
+        use std::cmp::Ordering;
+        use std::fmt;
+
+        use super::bounds::HasArea;
+        use crate::chapters::chapter_1::chapter_1::Point2D;
+
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum ShapeError {
+            TooFewPoints { needed: usize, found: usize },
+            // Every vertex lies on one line, or the lobes of a crossed
+            // polygon cancel out.
+            ZeroArea,
+            NonFinite(Point2D),
+            Tolerance(f64),
+        }
+
+        impl fmt::Display for ShapeError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    ShapeError::TooFewPoints { needed, found } => {
+                        write!(f, "need at least {} distinct points, got {}", needed, found)
+                    }
+                    ShapeError::ZeroArea => write!(f, "shape has zero area"),
+                    ShapeError::NonFinite(p) => write!(f, "point {} is not finite", p),
+                    ShapeError::Tolerance(epsilon) => {
+                        write!(
+                            f,
+                            "tolerance {} is not a finite, non-negative number",
+                            epsilon
+                        )
+                    }
+                }
+            }
+        }
+
+        impl std::error::Error for ShapeError {}
+
+        // The direction the vertices go round in, with y pointing up.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Orientation {
+            Clockwise,
+            CounterClockwise,
+        }
+
+        // How `Polygon::contains` treats regions a self-intersecting polygon
+        // wraps more than once. Both agree on simple polygons.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum FillRule {
+            EvenOdd,
+            NonZero,
+        }
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Polygon {
+            vertices: Vec<Point2D>,
+        }
+
+        impl Polygon {
+            // The edge back to the first vertex is implied; a repeated first
+            // vertex at the end is dropped.
+            pub fn new(mut vertices: Vec<Point2D>) -> Result<Polygon, ShapeError> {
+                check_finite(&vertices)?;
+                if vertices.len() > 1 && vertices.first() == vertices.last() {
+                    vertices.pop();
+                }
+                if vertices.len() < 3 {
+                    return Err(ShapeError::TooFewPoints {
+                        needed: 3,
+                        found: vertices.len(),
+                    });
+                }
+
+                let polygon = Polygon { vertices };
+                if polygon.signed_area() == 0.0 {
+                    return Err(ShapeError::ZeroArea);
+                }
+                Ok(polygon)
+            }
+
+            // Smallest convex polygon holding every point, counter-clockwise
+            // from the lowest-leftmost point (Andrew's monotone chain).
+            pub fn convex_hull(points: &[Point2D]) -> Result<Polygon, ShapeError> {
+                check_finite(points)?;
+                let mut points = points.to_vec();
+                points.sort_by(|a, b| a.x().total_cmp(&b.x()).then(a.y().total_cmp(&b.y())));
+                points.dedup();
+                if points.len() < 3 {
+                    return Err(ShapeError::TooFewPoints {
+                        needed: 3,
+                        found: points.len(),
+                    });
+                }
+
+                // Collinear points are dropped, so only corners are kept.
+                let mut hull: Vec<Point2D> = Vec::with_capacity(points.len() + 1);
+                let forward = points.iter();
+                let backward = points.iter().rev().skip(1);
+                let mut lower_len = 0;
+                for (i, &p) in forward.chain(backward).enumerate() {
+                    let floor = if i < points.len() { 0 } else { lower_len };
+                    while hull.len() >= floor + 2
+                        && turn(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0
+                    {
+                        hull.pop();
+                    }
+                    hull.push(p);
+                    if i + 1 == points.len() {
+                        lower_len = hull.len() - 1;
+                    }
+                }
+                // The walk ends where it started.
+                hull.pop();
+
+                if hull.len() < 3 {
+                    return Err(ShapeError::ZeroArea);
+                }
+                Ok(Polygon { vertices: hull })
+            }
+
+            pub fn vertices(&self) -> &[Point2D] {
+                &self.vertices
+            }
+
+            // Each edge as `(from, to)`, including the closing one.
+            pub fn edges(&self) -> impl Iterator<Item = (Point2D, Point2D)> + '_ {
+                let next = self.vertices.iter().cycle().skip(1);
+                self.vertices.iter().copied().zip(next.copied())
+            }
+
+            // Positive when counter-clockwise.
+            fn signed_area(&self) -> f64 {
+                self.edges().map(|(a, b)| a.cross(b)).sum::<f64>() / 2.0
+            }
+
+            pub fn perimeter(&self) -> f64 {
+                self.edges().map(|(a, b)| a.distance(b)).sum()
+            }
+
+            // Centre of mass of the enclosed region, not the mean of the
+            // vertices.
+            pub fn centroid(&self) -> Point2D {
+                let (mut x, mut y) = (0.0, 0.0);
+                for (a, b) in self.edges() {
+                    let cross = a.cross(b);
+                    x += (a.x() + b.x()) * cross;
+                    y += (a.y() + b.y()) * cross;
+                }
+                let scale = 6.0 * self.signed_area();
+                Point2D::new(x / scale, y / scale)
+            }
+
+            pub fn orientation(&self) -> Orientation {
+                if self.signed_area() > 0.0 {
+                    Orientation::CounterClockwise
+                } else {
+                    Orientation::Clockwise
+                }
+            }
+
+            pub fn reversed(&self) -> Polygon {
+                let mut vertices = self.vertices.clone();
+                vertices.reverse();
+                Polygon { vertices }
+            }
+
+            // No two edges cross or touch, other than neighbours meeting at
+            // their shared vertex. O(n²) in the number of edges.
+            pub fn is_simple(&self) -> bool {
+                let edges: Vec<_> = self.edges().collect();
+                let n = edges.len();
+                for i in 0..n {
+                    for j in i + 1..n {
+                        let (a, b) = edges[i];
+                        let (c, d) = edges[j];
+                        let neighbours = j == i + 1 || (i == 0 && j == n - 1);
+                        if !neighbours {
+                            // This also catches repeated vertices.
+                            if segments_intersect(a, b, c, d) {
+                                return false;
+                            }
+                            continue;
+                        }
+
+                        // Neighbours may only share their one vertex; one
+                        // doubling back along the other is a spike.
+                        let (shared, p, q) = if j == i + 1 { (b, a, d) } else { (a, b, c) };
+                        let (u, v) = (p - shared, q - shared);
+                        if u.cross(v) == 0.0 && u.dot(v) > 0.0 {
+                            return false;
+                        }
+                    }
+                }
+                true
+            }
+
+            // Points on the boundary count as inside under either rule.
+            pub fn contains(&self, point: Point2D, rule: FillRule) -> bool {
+                if self.edges().any(|(a, b)| on_segment(a, b, point)) {
+                    return true;
+                }
+                match rule {
+                    FillRule::EvenOdd => self.crossings(point) % 2 == 1,
+                    FillRule::NonZero => self.winding_number(point) != 0,
+                }
+            }
+
+            // How many times the boundary wraps counter-clockwise around
+            // `point`; clockwise turns count as negative.
+            pub fn winding_number(&self, point: Point2D) -> i32 {
+                let mut winding = 0;
+                for (a, b) in self.edges() {
+                    if a.y() <= point.y() {
+                        if b.y() > point.y() && turn(a, b, point) > 0.0 {
+                            winding += 1;
+                        }
+                    } else if b.y() <= point.y() && turn(a, b, point) < 0.0 {
+                        winding -= 1;
+                    }
+                }
+                winding
+            }
+
+            // Edges crossed by a ray from `point` towards +x.
+            fn crossings(&self, point: Point2D) -> usize {
+                self.edges()
+                    .filter(|&(a, b)| {
+                        (a.y() > point.y()) != (b.y() > point.y()) && {
+                            let t = (point.y() - a.y()) / (b.y() - a.y());
+                            point.x() < a.x() + t * (b.x() - a.x())
+                        }
+                    })
+                    .count()
+            }
+        }
+
+        impl HasArea for Polygon {
+            fn area(&self) -> f64 {
+                self.signed_area().abs()
+            }
+        }
+
+        // An open chain of points.
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Polyline {
+            points: Vec<Point2D>,
+        }
+
+        impl Polyline {
+            pub fn new(points: Vec<Point2D>) -> Result<Polyline, ShapeError> {
+                check_finite(&points)?;
+                if points.len() < 2 {
+                    return Err(ShapeError::TooFewPoints {
+                        needed: 2,
+                        found: points.len(),
+                    });
+                }
+                Ok(Polyline { points })
+            }
+
+            pub fn points(&self) -> &[Point2D] {
+                &self.points
+            }
+
+            pub fn length(&self) -> f64 {
+                self.points.windows(2).map(|w| w[0].distance(w[1])).sum()
+            }
+
+            // Douglas–Peucker: drops every point that lies within `epsilon`
+            // of the simplified line. The end points are always kept.
+            pub fn simplify(&self, epsilon: f64) -> Result<Polyline, ShapeError> {
+                if !epsilon.is_finite() || epsilon < 0.0 {
+                    return Err(ShapeError::Tolerance(epsilon));
+                }
+
+                let mut keep = vec![false; self.points.len()];
+                keep[0] = true;
+                keep[self.points.len() - 1] = true;
+                // An explicit stack rather than recursion, so long lines
+                // cannot overflow it.
+                let mut pending = vec![(0, self.points.len() - 1)];
+                while let Some((first, last)) = pending.pop() {
+                    let (start, end) = (self.points[first], self.points[last]);
+                    let farthest = (first + 1..last)
+                        .map(|i| (i, segment_distance(start, end, self.points[i])))
+                        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+                    if let Some((i, distance)) = farthest {
+                        if distance > epsilon {
+                            keep[i] = true;
+                            pending.push((first, i));
+                            pending.push((i, last));
+                        }
+                    }
+                }
+
+                let points = self.points.iter().zip(keep).filter(|(_, k)| *k);
+                Ok(Polyline {
+                    points: points.map(|(p, _)| *p).collect(),
+                })
+            }
+
+            // Joins the last point back to the first.
+            pub fn close(&self) -> Result<Polygon, ShapeError> {
+                Polygon::new(self.points.clone())
+            }
+        }
+
+        fn check_finite(points: &[Point2D]) -> Result<(), ShapeError> {
+            match points
+                .iter()
+                .find(|p| !p.x().is_finite() || !p.y().is_finite())
+            {
+                Some(&p) => Err(ShapeError::NonFinite(p)),
+                None => Ok(()),
+            }
+        }
+
+        // Positive when `a -> b -> c` turns left, zero when they're collinear.
+        fn turn(a: Point2D, b: Point2D, c: Point2D) -> f64 {
+            (b - a).cross(c - a)
+        }
+
+        fn on_segment(a: Point2D, b: Point2D, p: Point2D) -> bool {
+            turn(a, b, p) == 0.0
+                && p.x() >= a.x().min(b.x())
+                && p.x() <= a.x().max(b.x())
+                && p.y() >= a.y().min(b.y())
+                && p.y() <= a.y().max(b.y())
+        }
+
+        fn segments_intersect(a: Point2D, b: Point2D, c: Point2D, d: Point2D) -> bool {
+            let (d1, d2) = (turn(c, d, a), turn(c, d, b));
+            let (d3, d4) = (turn(a, b, c), turn(a, b, d));
+            if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+                && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+            {
+                return true;
+            }
+            on_segment(c, d, a) || on_segment(c, d, b) || on_segment(a, b, c) || on_segment(a, b, d)
+        }
+
+        fn segment_distance(a: Point2D, b: Point2D, p: Point2D) -> f64 {
+            let along = b - a;
+            let length_squared = along.dot(along);
+            if length_squared == 0.0 {
+                return p.distance(a);
+            }
+            let t = ((p - a).dot(along) / length_squared).clamp(0.0, 1.0);
+            p.distance(a + along * t)
+        }
+    }
+
     #[allow(dead_code)]
     pub mod empty_bound {
         // A consequence of how bounds ork is that even if a `trait` doesn't inlude any
@@ -377,9 +732,11 @@ mod test {
     };
 
     use self::generics::{
-        bounds::{Rectangle, Triangle},
+        bounds::{HasArea, Rectangle, Triangle},
         empty_bound::{BlueJay, Cardinal},
+        shapes::{FillRule, Orientation, Polygon, Polyline, ShapeError},
     };
+    use crate::chapters::chapter_1::chapter_1::Point2D;
 
     use super::*;
 
@@ -406,4 +763,176 @@ mod test {
         assert_eq!("blue", blue(&blue_jay));
         assert_eq!("red", red(&cardinal));
     }
+
+    fn points(coords: &[(f64, f64)]) -> Vec<Point2D> {
+        coords.iter().map(|&(x, y)| Point2D::new(x, y)).collect()
+    }
+
+    #[test]
+    fn polygon_measures() {
+        let square =
+            Polygon::new(points(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)])).unwrap();
+        assert_eq!(16.0, area(&square));
+        assert_eq!(16.0, square.perimeter());
+        assert_eq!(Point2D::new(2.0, 2.0), square.centroid());
+        assert_eq!(Orientation::CounterClockwise, square.orientation());
+        assert_eq!(Orientation::Clockwise, square.reversed().orientation());
+        assert_eq!(16.0, square.reversed().area());
+        assert!(square.is_simple());
+
+        // A closing vertex is optional.
+        let closed = Polygon::new(points(&[
+            (0.0, 0.0),
+            (4.0, 0.0),
+            (4.0, 4.0),
+            (0.0, 4.0),
+            (0.0, 0.0),
+        ]));
+        assert_eq!(Ok(square), closed);
+
+        // An L shape: the centroid is pulled towards the long arm.
+        let l_shape = Polygon::new(points(&[
+            (0.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 1.0),
+            (1.0, 1.0),
+            (1.0, 2.0),
+            (0.0, 2.0),
+        ]))
+        .unwrap();
+        assert_eq!(3.0, l_shape.area());
+        let centroid = l_shape.centroid();
+        assert!((centroid.x() - 5.0 / 6.0).abs() < 1e-12);
+        assert!((centroid.y() - 5.0 / 6.0).abs() < 1e-12);
+
+        let bow_tie =
+            Polygon::new(points(&[(0.0, 0.0), (4.0, 4.0), (4.0, 0.0), (0.0, 2.0)])).unwrap();
+        assert!(!bow_tie.is_simple());
+        let spike =
+            Polygon::new(points(&[(0.0, 0.0), (4.0, 0.0), (2.0, 0.0), (2.0, 3.0)])).unwrap();
+        assert!(!spike.is_simple());
+    }
+
+    #[test]
+    fn polygon_containment() {
+        let square =
+            Polygon::new(points(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)])).unwrap();
+        for rule in [FillRule::EvenOdd, FillRule::NonZero] {
+            assert!(square.contains(Point2D::new(1.0, 3.0), rule));
+            assert!(square.contains(Point2D::new(4.0, 2.0), rule));
+            assert!(square.contains(Point2D::new(0.0, 0.0), rule));
+            assert!(!square.contains(Point2D::new(5.0, 2.0), rule));
+            assert!(!square.contains(Point2D::new(-1.0, 0.0), rule));
+        }
+        assert_eq!(1, square.winding_number(Point2D::new(2.0, 2.0)));
+        assert_eq!(-1, square.reversed().winding_number(Point2D::new(2.0, 2.0)));
+
+        // A pentagram wraps its middle twice, so the rules disagree there.
+        let star = Polygon::new(points(&[
+            (0.0, 10.0),
+            (6.0, -8.0),
+            (-9.5, 3.0),
+            (9.5, 3.0),
+            (-6.0, -8.0),
+        ]))
+        .unwrap();
+        let middle = Point2D::new(0.0, 0.0);
+        assert_eq!(2, star.winding_number(middle).abs());
+        assert!(star.contains(middle, FillRule::NonZero));
+        assert!(!star.contains(middle, FillRule::EvenOdd));
+        let tip = Point2D::new(0.0, 8.0);
+        assert!(star.contains(tip, FillRule::NonZero));
+        assert!(star.contains(tip, FillRule::EvenOdd));
+    }
+
+    #[test]
+    fn hull_and_simplify() {
+        let cloud = points(&[
+            (0.0, 0.0),
+            (1.0, 1.0),
+            (2.0, 2.0),
+            (2.0, 0.0),
+            (1.0, 0.0),
+            (0.0, 2.0),
+            (1.0, 3.0),
+            (0.5, 1.5),
+        ]);
+        let hull = Polygon::convex_hull(&cloud).unwrap();
+        assert_eq!(
+            points(&[(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (1.0, 3.0), (0.0, 2.0)]),
+            hull.vertices()
+        );
+        assert_eq!(Orientation::CounterClockwise, hull.orientation());
+
+        let line = Polyline::new(points(&[
+            (0.0, 0.0),
+            (1.0, 0.1),
+            (2.0, -0.1),
+            (3.0, 5.0),
+            (4.0, 6.0),
+            (5.0, 7.0),
+            (6.0, 8.1),
+            (7.0, 9.0),
+        ]))
+        .unwrap();
+        let simple = line.simplify(0.5).unwrap();
+        assert_eq!(
+            points(&[(0.0, 0.0), (2.0, -0.1), (3.0, 5.0), (7.0, 9.0)]),
+            simple.points()
+        );
+        // Only points exactly on the line go at zero tolerance.
+        assert_eq!(7, line.simplify(0.0).unwrap().points().len());
+        assert_eq!(2, line.simplify(100.0).unwrap().points().len());
+        assert!(simple.length() <= line.length());
+
+        let triangle = Polyline::new(points(&[(0.0, 0.0), (3.0, 0.0), (0.0, 4.0)])).unwrap();
+        assert_eq!(8.0, triangle.length());
+        assert_eq!(12.0, triangle.close().unwrap().perimeter());
+    }
+
+    #[test]
+    fn shape_errors() {
+        assert_eq!(
+            Err(ShapeError::TooFewPoints {
+                needed: 3,
+                found: 2
+            }),
+            Polygon::new(points(&[(0.0, 0.0), (1.0, 1.0)]))
+        );
+        assert_eq!(
+            Err(ShapeError::ZeroArea),
+            Polygon::new(points(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]))
+        );
+        assert_eq!(
+            Err(ShapeError::NonFinite(Point2D::new(f64::INFINITY, 0.0))),
+            Polygon::new(points(&[(0.0, 0.0), (f64::INFINITY, 0.0), (1.0, 1.0)]))
+        );
+        assert!(Polygon::new(points(&[(0.0, 0.0), (f64::NAN, 0.0), (1.0, 1.0)])).is_err());
+        assert_eq!(
+            Err(ShapeError::ZeroArea),
+            Polygon::convex_hull(&points(&[(0.0, 0.0), (1.0, 1.0), (3.0, 3.0), (2.0, 2.0)]))
+        );
+        assert_eq!(
+            Err(ShapeError::TooFewPoints {
+                needed: 3,
+                found: 2
+            }),
+            Polygon::convex_hull(&points(&[(0.0, 0.0), (1.0, 1.0), (0.0, 0.0)]))
+        );
+        assert_eq!(
+            Err(ShapeError::TooFewPoints {
+                needed: 2,
+                found: 1
+            }),
+            Polyline::new(points(&[(0.0, 0.0)]))
+        );
+
+        let line = Polyline::new(points(&[(0.0, 0.0), (1.0, 1.0)])).unwrap();
+        assert_eq!(Err(ShapeError::Tolerance(-1.0)), line.simplify(-1.0));
+        assert!(line.simplify(f64::NAN).is_err());
+        assert_eq!(
+            "need at least 3 distinct points, got 2",
+            line.close().unwrap_err().to_string()
+        );
+    }
 }
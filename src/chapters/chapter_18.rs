@@ -25,6 +25,10 @@ pub mod error_handling {
     }
 
     pub mod options_qmark {
+        use core::fmt;
+
+        use crate::chapters::chapter_1::chapter_1::redact::{Inspect, Node};
+
         pub struct Person {
             pub job: Option<Job>,
         }
@@ -46,6 +50,47 @@ pub mod error_handling {
                 self.job?.phone_number?.area_code
             }
         }
+
+        // Phone numbers are personal, so `number` is a secret and even `{:?}`
+        // only shows its last four digits unless the field is redacted.
+        impl Inspect for Person {
+            fn inspect(&self) -> Node {
+                Node::record("Person").field("job", &self.job).finish()
+            }
+        }
+
+        impl Inspect for Job {
+            fn inspect(&self) -> Node {
+                Node::record("Job").field("phone_number", &self.phone_number).finish()
+            }
+        }
+
+        impl Inspect for PhoneNumber {
+            fn inspect(&self) -> Node {
+                Node::record("PhoneNumber")
+                    .field("area_code", &self.area_code)
+                    .secret_field_tail("number", &self.number, 4)
+                    .finish()
+            }
+        }
+
+        impl fmt::Debug for Person {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.redacted().fmt(f)
+            }
+        }
+
+        impl fmt::Debug for Job {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.redacted().fmt(f)
+            }
+        }
+
+        impl fmt::Debug for PhoneNumber {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.redacted().fmt(f)
+            }
+        }
     }
 }
 
@@ -53,6 +98,8 @@ pub mod error_handling {
 mod test {
     use crate::chapters::chapter_18::error_handling::abort_unwind::drink;
 
+    use crate::chapters::chapter_1::chapter_1::redact::DebugOptions;
+
     use super::error_handling::{options_qmark::{Person, Job, PhoneNumber}, panic};
 
     #[test]
@@ -75,4 +122,29 @@ mod test {
          assert_eq!(69, person.work_phone_area_code().unwrap())
             
     }
+
+    #[test]
+    fn redacted_debug() {
+        let person = Person {
+            job: Some(Job { phone_number: Some(PhoneNumber { area_code: None, number: 4206969 }) })
+        };
+
+        assert_eq!(
+            "Person { job: Some(Job { phone_number: Some(PhoneNumber { area_code: None, number: \"***6969\" }) }) }",
+            format!("{:?}", person)
+        );
+        assert_eq!(
+            "Person { job: Some(Job { phone_number: Some(PhoneNumber { number: \"***\" }) }) }",
+            format!("{:?}", DebugOptions::new().hide_none(true).redact("number").show(&person))
+        );
+        assert_eq!(
+            "Person { job: Some(Job { .. }) }",
+            format!("{:?}", DebugOptions::new().max_depth(1).show(&person))
+        );
+        assert_eq!(
+            "Person { job: \"***\" }",
+            format!("{:?}", DebugOptions::new().redact("job").show(&person))
+        );
+        assert!(!format!("{:#?}", person).contains("4206969"));
+    }
 }
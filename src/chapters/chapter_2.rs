@@ -1,4 +1,1190 @@
-pub mod chapter_2 {}
+pub mod chapter_2 {
+    use core::fmt;
+    use std::ops::{Add, Index, IndexMut, Mul, Sub};
+
+    // An `R` by `C` matrix of `f64`, stored row by row. The dimensions are
+    // part of the type, so multiplying a 2×3 by anything but a 3×N is a
+    // compile error rather than a panic.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Matrix<const R: usize, const C: usize> {
+        rows: [[f64; C]; R],
+    }
+
+    #[allow(dead_code)]
+    impl<const R: usize, const C: usize> Matrix<R, C> {
+        pub fn new(rows: [[f64; C]; R]) -> Matrix<R, C> {
+            Matrix { rows }
+        }
+
+        pub fn zeros() -> Matrix<R, C> {
+            Matrix {
+                rows: [[0.0; C]; R],
+            }
+        }
+
+        // Each entry is `f(row, column)`.
+        pub fn from_fn(f: impl Fn(usize, usize) -> f64) -> Matrix<R, C> {
+            Matrix {
+                rows: std::array::from_fn(|row| std::array::from_fn(|column| f(row, column))),
+            }
+        }
+
+        pub fn rows(&self) -> &[[f64; C]; R] {
+            &self.rows
+        }
+
+        pub fn transpose(&self) -> Matrix<C, R> {
+            Matrix::from_fn(|row, column| self.rows[column][row])
+        }
+
+        pub fn approx_eq(&self, other: &Matrix<R, C>, epsilon: f64) -> bool {
+            self.rows
+                .iter()
+                .flatten()
+                .zip(other.rows.iter().flatten())
+                .all(|(a, b)| (a - b).abs() <= epsilon)
+        }
+    }
+
+    #[allow(dead_code)]
+    impl<const N: usize> Matrix<N, N> {
+        pub fn identity() -> Matrix<N, N> {
+            Matrix::from_fn(|row, column| if row == column { 1.0 } else { 0.0 })
+        }
+
+        // Gaussian elimination with partial pivoting. The determinant of the
+        // empty 0×0 matrix is 1.
+        pub fn determinant(&self) -> f64 {
+            let mut a = self.rows;
+            let mut determinant = 1.0;
+            for column in 0..N {
+                let pivot = pivot_row(&a, column);
+                if a[pivot][column] == 0.0 {
+                    return 0.0;
+                }
+                if pivot != column {
+                    a.swap(pivot, column);
+                    determinant = -determinant;
+                }
+                determinant *= a[column][column];
+
+                let pivot_row = a[column];
+                for row in &mut a[column + 1..] {
+                    let factor = row[column] / pivot_row[column];
+                    for (x, p) in row.iter_mut().zip(pivot_row).skip(column) {
+                        *x -= factor * p;
+                    }
+                }
+            }
+            determinant
+        }
+
+        // Gauss–Jordan elimination. `None` when the matrix is singular, or
+        // so close to it that the result would be mostly rounding error.
+        pub fn inverse(&self) -> Option<Matrix<N, N>> {
+            let scale = self
+                .rows
+                .iter()
+                .flatten()
+                .fold(0.0, |m: f64, x| m.max(x.abs()));
+            let tolerance = scale * f64::EPSILON * N as f64;
+
+            let mut a = self.rows;
+            let mut inverse = Matrix::<N, N>::identity().rows;
+            for column in 0..N {
+                let pivot = pivot_row(&a, column);
+                if a[pivot][column].abs() <= tolerance {
+                    return None;
+                }
+                a.swap(pivot, column);
+                inverse.swap(pivot, column);
+
+                let p = a[column][column];
+                a[column].iter_mut().for_each(|x| *x /= p);
+                inverse[column].iter_mut().for_each(|x| *x /= p);
+
+                let (pivot_row, pivot_inverse) = (a[column], inverse[column]);
+                for row in (0..N).filter(|&row| row != column) {
+                    let factor = a[row][column];
+                    for (x, p) in a[row].iter_mut().zip(pivot_row) {
+                        *x -= factor * p;
+                    }
+                    for (x, p) in inverse[row].iter_mut().zip(pivot_inverse) {
+                        *x -= factor * p;
+                    }
+                }
+            }
+            Some(Matrix { rows: inverse })
+        }
+    }
+
+    // The row at or below `column` with the largest entry in that column.
+    fn pivot_row<const N: usize>(a: &[[f64; N]; N], column: usize) -> usize {
+        (column..N)
+            .max_by(|&i, &j| a[i][column].abs().total_cmp(&a[j][column].abs()))
+            .unwrap_or(column)
+    }
+
+    // `matrix[(row, column)]`
+    impl<const R: usize, const C: usize> Index<(usize, usize)> for Matrix<R, C> {
+        type Output = f64;
+
+        fn index(&self, (row, column): (usize, usize)) -> &f64 {
+            &self.rows[row][column]
+        }
+    }
+
+    impl<const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<R, C> {
+        fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut f64 {
+            &mut self.rows[row][column]
+        }
+    }
+
+    impl<const R: usize, const C: usize> From<[[f64; C]; R]> for Matrix<R, C> {
+        fn from(rows: [[f64; C]; R]) -> Matrix<R, C> {
+            Matrix { rows }
+        }
+    }
+
+    impl<const R: usize, const C: usize> From<Matrix<R, C>> for [[f64; C]; R] {
+        fn from(matrix: Matrix<R, C>) -> [[f64; C]; R] {
+            matrix.rows
+        }
+    }
+
+    // The tuple struct from the RBE exercise, read row by row.
+    impl From<(f64, f64, f64, f64)> for Matrix<2, 2> {
+        fn from((a, b, c, d): (f64, f64, f64, f64)) -> Matrix<2, 2> {
+            Matrix::new([[a, b], [c, d]])
+        }
+    }
+
+    impl<const R: usize, const C: usize> Add for Matrix<R, C> {
+        type Output = Matrix<R, C>;
+
+        fn add(self, rhs: Matrix<R, C>) -> Matrix<R, C> {
+            Matrix::from_fn(|row, column| self.rows[row][column] + rhs.rows[row][column])
+        }
+    }
+
+    impl<const R: usize, const C: usize> Sub for Matrix<R, C> {
+        type Output = Matrix<R, C>;
+
+        fn sub(self, rhs: Matrix<R, C>) -> Matrix<R, C> {
+            Matrix::from_fn(|row, column| self.rows[row][column] - rhs.rows[row][column])
+        }
+    }
+
+    impl<const R: usize, const C: usize> Mul<f64> for Matrix<R, C> {
+        type Output = Matrix<R, C>;
+
+        fn mul(self, rhs: f64) -> Matrix<R, C> {
+            Matrix::from_fn(|row, column| self.rows[row][column] * rhs)
+        }
+    }
+
+    // The inner dimensions have to agree: `C` here is both our column count
+    // and the right-hand side's row count.
+    impl<const R: usize, const C: usize, const K: usize> Mul<Matrix<C, K>> for Matrix<R, C> {
+        type Output = Matrix<R, K>;
+
+        fn mul(self, rhs: Matrix<C, K>) -> Matrix<R, K> {
+            Matrix::from_fn(|row, column| {
+                (0..C)
+                    .map(|i| self.rows[row][i] * rhs.rows[i][column])
+                    .sum()
+            })
+        }
+    }
+
+    // One row per line, as in the RBE exercise: `( 1.1 1.2 )`. Entries are
+    // right-aligned per column and honor precision.
+    impl<const R: usize, const C: usize> fmt::Display for Matrix<R, C> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let cells: Vec<Vec<String>> = self
+                .rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|x| match f.precision() {
+                            Some(precision) => format!("{:.*}", precision, x),
+                            None => x.to_string(),
+                        })
+                        .collect()
+                })
+                .collect();
+            let widths: Vec<usize> = (0..C)
+                .map(|column| cells.iter().map(|row| row[column].len()).max().unwrap_or(0))
+                .collect();
+
+            for (count, row) in cells.iter().enumerate() {
+                if count != 0 {
+                    writeln!(f)?;
+                }
+                write!(f, "(")?;
+                for (cell, width) in row.iter().zip(&widths) {
+                    write!(f, " {:>width$}", cell, width = width)?;
+                }
+                write!(f, " )")?;
+            }
+            Ok(())
+        }
+    }
+
+    // How a primitive value is laid out in memory: its bits, its bytes in
+    // either order, what those bits mean, and what `as` does to it for every
+    // other primitive type.
+    #[allow(dead_code)]
+    pub mod representation {
+        use core::fmt;
+        use std::num::FpCategory;
+        use std::str::FromStr;
+
+        use crate::chapters::chapter_1::chapter_1::parse::{Expected, ParseError};
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Kind {
+            Signed,
+            Unsigned,
+            Float,
+        }
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Representation {
+            pub type_name: &'static str,
+            pub kind: Kind,
+            pub bits: u32,
+            // The value as `Display` writes it.
+            pub value: String,
+            // Every bit, most significant first.
+            pub binary: String,
+            pub big_endian: Vec<u8>,
+            pub little_endian: Vec<u8>,
+            pub layout: Layout,
+            pub casts: Vec<Cast>,
+        }
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum Layout {
+            Unsigned,
+            // A negative value is stored as its magnitude with every bit
+            // flipped, plus one. `inverted` is `None` for non-negative values.
+            TwosComplement {
+                magnitude: String,
+                inverted: Option<String>,
+            },
+            Float(FloatParts),
+        }
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct FloatParts {
+            pub negative: bool,
+            pub class: FpCategory,
+            pub exponent_bits: String,
+            pub biased_exponent: u32,
+            // With the bias taken off; `None` for infinities and NaN.
+            pub exponent: Option<i32>,
+            pub mantissa_bits: String,
+            // The mantissa with its implicit leading bit: `1.x` for normal
+            // values, `0.x` for subnormal ones and zero.
+            pub significand: Option<f64>,
+        }
+
+        // What `value as target` gave.
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Cast {
+            pub target: &'static str,
+            pub result: String,
+            pub effect: CastEffect,
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum CastEffect {
+            // The number is unchanged.
+            Exact,
+            // High bits were dropped.
+            Truncated,
+            // A negative value widened into an unsigned type: the sign bit
+            // was copied into the new high bits.
+            SignExtended,
+            // Same width, the other signedness: the bits are read differently.
+            Reinterpreted,
+            // To the nearest representable float.
+            Rounded,
+            // A float's fractional part was dropped.
+            TowardZero,
+            // A float outside the target's range was clamped to its min or max.
+            Saturated,
+            NanToZero,
+            // A value too big for `f32` became infinite.
+            Overflowed,
+        }
+
+        impl fmt::Display for CastEffect {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let text = match self {
+                    CastEffect::Exact => "exact",
+                    CastEffect::Truncated => "truncated",
+                    CastEffect::SignExtended => "sign-extended",
+                    CastEffect::Reinterpreted => "reinterpreted",
+                    CastEffect::Rounded => "rounded",
+                    CastEffect::TowardZero => "rounded toward zero",
+                    CastEffect::Saturated => "saturated",
+                    CastEffect::NanToZero => "NaN becomes 0",
+                    CastEffect::Overflowed => "overflowed to infinity",
+                };
+                write!(f, "{}", text)
+            }
+        }
+
+        pub trait Primitive: Copy {
+            const NAME: &'static str;
+
+            fn explain(self) -> Representation;
+        }
+
+        // Any primitive value, widened without loss so values of different
+        // types can be compared.
+        #[derive(Debug, Clone, Copy)]
+        enum Number {
+            Int(i128),
+            UInt(u128),
+            Float(f64),
+        }
+
+        impl Number {
+            fn is_negative(self) -> bool {
+                match self {
+                    Number::Int(value) => value < 0,
+                    Number::UInt(_) => false,
+                    Number::Float(value) => value < 0.0,
+                }
+            }
+
+            fn same_value(self, other: Number) -> bool {
+                // 2^127 and 2^128, where `i128` and `u128` run out.
+                const I128_END: f64 = 170141183460469231731687303715884105728.0;
+                const U128_END: f64 = 340282366920938463463374607431768211456.0;
+
+                match (self, other) {
+                    (Number::Int(a), Number::Int(b)) => a == b,
+                    (Number::UInt(a), Number::UInt(b)) => a == b,
+                    (Number::Int(a), Number::UInt(b)) | (Number::UInt(b), Number::Int(a)) => {
+                        u128::try_from(a) == Ok(b)
+                    }
+                    (Number::Float(a), Number::Float(b)) => a == b || (a.is_nan() && b.is_nan()),
+                    (Number::Float(x), Number::Int(i)) | (Number::Int(i), Number::Float(x)) => {
+                        x.fract() == 0.0 && (-I128_END..I128_END).contains(&x) && x as i128 == i
+                    }
+                    (Number::Float(x), Number::UInt(u)) | (Number::UInt(u), Number::Float(x)) => {
+                        x.fract() == 0.0 && (0.0..U128_END).contains(&x) && x as u128 == u
+                    }
+                }
+            }
+        }
+
+        // Every primitive `as` cast of `value`, widened back into a `Number`
+        // and as the target type's `Display` writes it.
+        macro_rules! all_casts {
+            ($value:expr) => {
+                [
+                    (
+                        "i8",
+                        Kind::Signed,
+                        8,
+                        Number::Int($value as i8 as i128),
+                        ($value as i8).to_string(),
+                    ),
+                    (
+                        "i16",
+                        Kind::Signed,
+                        16,
+                        Number::Int($value as i16 as i128),
+                        ($value as i16).to_string(),
+                    ),
+                    (
+                        "i32",
+                        Kind::Signed,
+                        32,
+                        Number::Int($value as i32 as i128),
+                        ($value as i32).to_string(),
+                    ),
+                    (
+                        "i64",
+                        Kind::Signed,
+                        64,
+                        Number::Int($value as i64 as i128),
+                        ($value as i64).to_string(),
+                    ),
+                    (
+                        "i128",
+                        Kind::Signed,
+                        128,
+                        Number::Int($value as i128),
+                        ($value as i128).to_string(),
+                    ),
+                    (
+                        "isize",
+                        Kind::Signed,
+                        isize::BITS,
+                        Number::Int($value as isize as i128),
+                        ($value as isize).to_string(),
+                    ),
+                    (
+                        "u8",
+                        Kind::Unsigned,
+                        8,
+                        Number::UInt($value as u8 as u128),
+                        ($value as u8).to_string(),
+                    ),
+                    (
+                        "u16",
+                        Kind::Unsigned,
+                        16,
+                        Number::UInt($value as u16 as u128),
+                        ($value as u16).to_string(),
+                    ),
+                    (
+                        "u32",
+                        Kind::Unsigned,
+                        32,
+                        Number::UInt($value as u32 as u128),
+                        ($value as u32).to_string(),
+                    ),
+                    (
+                        "u64",
+                        Kind::Unsigned,
+                        64,
+                        Number::UInt($value as u64 as u128),
+                        ($value as u64).to_string(),
+                    ),
+                    (
+                        "u128",
+                        Kind::Unsigned,
+                        128,
+                        Number::UInt($value as u128),
+                        ($value as u128).to_string(),
+                    ),
+                    (
+                        "usize",
+                        Kind::Unsigned,
+                        usize::BITS,
+                        Number::UInt($value as usize as u128),
+                        ($value as usize).to_string(),
+                    ),
+                    (
+                        "f32",
+                        Kind::Float,
+                        32,
+                        Number::Float($value as f32 as f64),
+                        ($value as f32).to_string(),
+                    ),
+                    (
+                        "f64",
+                        Kind::Float,
+                        64,
+                        Number::Float($value as f64),
+                        ($value as f64).to_string(),
+                    ),
+                ]
+            };
+        }
+
+        macro_rules! integer_primitive {
+            ($kind:ident $number:ident $wide:ty: $($ty:ty),*) => {$(
+                impl Primitive for $ty {
+                    const NAME: &'static str = stringify!($ty);
+
+                    fn explain(self) -> Representation {
+                        let bits = <$ty>::BITS;
+                        describe(
+                            (Self::NAME, Kind::$kind, bits),
+                            (self.to_string(), Number::$number(self as $wide)),
+                            self as u128 & (u128::MAX >> (128 - bits)),
+                            self.to_be_bytes().to_vec(),
+                            &all_casts!(self),
+                        )
+                    }
+                }
+            )*};
+        }
+
+        integer_primitive!(Signed Int i128: i8, i16, i32, i64, i128, isize);
+        integer_primitive!(Unsigned UInt u128: u8, u16, u32, u64, u128, usize);
+
+        macro_rules! float_primitive {
+            ($($ty:ty),*) => {$(
+                impl Primitive for $ty {
+                    const NAME: &'static str = stringify!($ty);
+
+                    fn explain(self) -> Representation {
+                        describe(
+                            (Self::NAME, Kind::Float, std::mem::size_of::<$ty>() as u32 * 8),
+                            (self.to_string(), Number::Float(self as f64)),
+                            self.to_bits() as u128,
+                            self.to_be_bytes().to_vec(),
+                            &all_casts!(self),
+                        )
+                    }
+                }
+            )*};
+        }
+
+        float_primitive!(f32, f64);
+
+        fn describe(
+            (type_name, kind, bits): (&'static str, Kind, u32),
+            (text, value): (String, Number),
+            raw: u128,
+            big_endian: Vec<u8>,
+            casts: &[(&'static str, Kind, u32, Number, String)],
+        ) -> Representation {
+            let binary = format!("{:0width$b}", raw, width = bits as usize);
+            let layout = match kind {
+                Kind::Unsigned => Layout::Unsigned,
+                Kind::Signed => {
+                    let magnitude = match value {
+                        Number::Int(v) => v.unsigned_abs(),
+                        _ => unreachable!("signed integers are widened to `Number::Int`"),
+                    };
+                    let magnitude = format!("{:0width$b}", magnitude, width = bits as usize);
+                    let inverted = value.is_negative().then(|| {
+                        magnitude
+                            .chars()
+                            .map(|c| if c == '0' { '1' } else { '0' })
+                            .collect()
+                    });
+                    Layout::TwosComplement {
+                        magnitude,
+                        inverted,
+                    }
+                }
+                Kind::Float => Layout::Float(float_parts(&binary, value)),
+            };
+
+            let mut little_endian = big_endian.clone();
+            little_endian.reverse();
+            Representation {
+                type_name,
+                kind,
+                bits,
+                value: text,
+                binary,
+                big_endian,
+                little_endian,
+                layout,
+                casts: casts
+                    .iter()
+                    .filter(|(target, ..)| *target != type_name)
+                    .map(|(target, target_kind, target_bits, result, text)| Cast {
+                        target,
+                        result: text.clone(),
+                        effect: cast_effect(
+                            (kind, bits, value),
+                            (*target_kind, *target_bits),
+                            *result,
+                        ),
+                    })
+                    .collect(),
+            }
+        }
+
+        // `binary` holds the sign bit, then the exponent, then the mantissa;
+        // the exponent is 8 bits wide in an `f32` and 11 in an `f64`.
+        fn float_parts(binary: &str, value: Number) -> FloatParts {
+            let exponent_len = if binary.len() == 32 { 8 } else { 11 };
+            let exponent_bits = &binary[1..1 + exponent_len];
+            let mantissa_bits = &binary[1 + exponent_len..];
+            let bias = (1 << (exponent_len - 1)) - 1;
+            let biased_exponent = u32::from_str_radix(exponent_bits, 2).expect("binary digits");
+            let mantissa = u64::from_str_radix(mantissa_bits, 2).expect("binary digits");
+            let fraction = mantissa as f64 / (1u64 << mantissa_bits.len()) as f64;
+
+            let class = match value {
+                Number::Float(v) if binary.len() == 32 => (v as f32).classify(),
+                Number::Float(v) => v.classify(),
+                _ => unreachable!("floats are widened to `Number::Float`"),
+            };
+            let (exponent, significand) = match class {
+                FpCategory::Normal => (Some(biased_exponent as i32 - bias), Some(1.0 + fraction)),
+                FpCategory::Subnormal | FpCategory::Zero => (Some(1 - bias), Some(fraction)),
+                FpCategory::Infinite | FpCategory::Nan => (None, None),
+            };
+            FloatParts {
+                negative: binary.starts_with('1'),
+                class,
+                exponent_bits: exponent_bits.to_owned(),
+                biased_exponent,
+                exponent,
+                mantissa_bits: mantissa_bits.to_owned(),
+                significand,
+            }
+        }
+
+        fn cast_effect(
+            (kind, bits, value): (Kind, u32, Number),
+            (target_kind, target_bits): (Kind, u32),
+            result: Number,
+        ) -> CastEffect {
+            if value.same_value(result) {
+                return CastEffect::Exact;
+            }
+            match (kind, target_kind) {
+                (_, Kind::Float) => match result {
+                    Number::Float(r) if r.is_infinite() => CastEffect::Overflowed,
+                    _ => CastEffect::Rounded,
+                },
+                (Kind::Float, _) => match value {
+                    Number::Float(v) if v.is_nan() => CastEffect::NanToZero,
+                    Number::Float(v) if Number::Float(v.trunc()).same_value(result) => {
+                        CastEffect::TowardZero
+                    }
+                    _ => CastEffect::Saturated,
+                },
+                _ if target_bits < bits => CastEffect::Truncated,
+                _ if target_bits > bits => CastEffect::SignExtended,
+                _ => CastEffect::Reinterpreted,
+            }
+        }
+
+        // Hex bytes separated by spaces: `be 20 00 00`.
+        fn hex_bytes(bytes: &[u8]) -> String {
+            let hex: Vec<String> = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+            hex.join(" ")
+        }
+
+        impl fmt::Display for Representation {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                writeln!(f, "{}: {}, {} bits", self.value, self.type_name, self.bits)?;
+                match &self.layout {
+                    Layout::Unsigned => writeln!(f, "  binary:        {}", self.binary)?,
+                    Layout::TwosComplement {
+                        magnitude,
+                        inverted,
+                    } => {
+                        writeln!(f, "  binary:        {}", self.binary)?;
+                        match inverted {
+                            Some(inverted) => writeln!(
+                                f,
+                                "  negative:      magnitude {}, inverted {}, plus one {}",
+                                magnitude, inverted, self.binary
+                            )?,
+                            None => writeln!(f, "  non-negative:  stored as is")?,
+                        }
+                    }
+                    Layout::Float(parts) => {
+                        writeln!(
+                            f,
+                            "  binary:        {} {} {}",
+                            &self.binary[..1],
+                            parts.exponent_bits,
+                            parts.mantissa_bits
+                        )?;
+                        writeln!(f, "  class:         {}", class_name(parts.class))?;
+                        let sign = if parts.negative {
+                            "negative"
+                        } else {
+                            "positive"
+                        };
+                        writeln!(f, "  sign:          {} ({})", &self.binary[..1], sign)?;
+                        match parts.exponent {
+                            Some(exponent) if parts.class == FpCategory::Normal => writeln!(
+                                f,
+                                "  exponent:      {} = {}, unbiased {}",
+                                parts.exponent_bits, parts.biased_exponent, exponent
+                            )?,
+                            Some(exponent) => writeln!(
+                                f,
+                                "  exponent:      {} (all zeros), fixed at {}",
+                                parts.exponent_bits, exponent
+                            )?,
+                            None => {
+                                writeln!(f, "  exponent:      {} (all ones)", parts.exponent_bits)?
+                            }
+                        }
+                        write!(f, "  mantissa:      {}", parts.mantissa_bits)?;
+                        match (parts.significand, parts.exponent) {
+                            (Some(significand), Some(exponent)) => {
+                                writeln!(f, " (significand {})", significand)?;
+                                let sign = if parts.negative { "-" } else { "" };
+                                writeln!(
+                                    f,
+                                    "  value:         {}{} × 2^{}",
+                                    sign, significand, exponent
+                                )?;
+                            }
+                            _ => writeln!(f)?,
+                        }
+                    }
+                }
+                writeln!(f, "  big-endian:    {}", hex_bytes(&self.big_endian))?;
+                writeln!(f, "  little-endian: {}", hex_bytes(&self.little_endian))?;
+                write!(f, "  casts:")?;
+                for cast in &self.casts {
+                    write!(
+                        f,
+                        "\n    as {:<5} {} ({})",
+                        cast.target, cast.result, cast.effect
+                    )?;
+                }
+                Ok(())
+            }
+        }
+
+        fn class_name(class: FpCategory) -> &'static str {
+            match class {
+                FpCategory::Nan => "NaN",
+                FpCategory::Infinite => "infinite",
+                FpCategory::Zero => "zero",
+                FpCategory::Subnormal => "subnormal",
+                FpCategory::Normal => "normal",
+            }
+        }
+
+        // A Rust literal such as `-5i8`, `0x80_u8`, `0.1f32` or `1e300`.
+        // Without a suffix, literals with a `.`, an exponent, `inf` or `NaN`
+        // are `f64` and the rest `i32`, as in Rust. Errors point at the
+        // suffix or the first character that can't be part of the number.
+        impl FromStr for Representation {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Representation, ParseError> {
+                const SUFFIXES: &[&str] = &[
+                    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128",
+                    "usize", "f32", "f64",
+                ];
+                let error = |offset, expected| ParseError { offset, expected };
+                if s.is_empty() {
+                    return Err(error(
+                        0,
+                        Expected::Value("a primitive literal such as -5i8 or 0.1f32"),
+                    ));
+                }
+
+                let sign_len = usize::from(s.starts_with('-'));
+                let radix = [
+                    ("0x", 16, "a hex digit"),
+                    ("0o", 8, "an octal digit"),
+                    ("0b", 2, "a binary digit"),
+                ]
+                .into_iter()
+                .find(|(prefix, _, _)| s[sign_len..].starts_with(prefix));
+                let digits_start = sign_len + radix.map_or(0, |(prefix, _, _)| prefix.len());
+
+                // Only decimal literals can be floats, and in hex `f32` is
+                // just three more digits, so `0x1f32` is an `i32` as in Rust.
+                let suffix = SUFFIXES.iter().copied().find(|suffix| {
+                    (radix.is_none() || !suffix.starts_with('f'))
+                        && s.ends_with(suffix)
+                        && s.len() - suffix.len() >= digits_start
+                });
+                let suffix_start = s.len() - suffix.map_or(0, str::len);
+                let digits = &s[digits_start..suffix_start];
+                let cleaned = digits.replace('_', "");
+
+                let is_float = suffix.is_some_and(|suffix| suffix.starts_with('f'))
+                    || (radix.is_none()
+                        && (digits.contains(['.', 'e', 'E'])
+                            || ["inf", "infinity", "NaN"].contains(&digits)));
+                let suffix = match suffix {
+                    Some(suffix) => suffix,
+                    None if is_float => "f64",
+                    None => "i32",
+                };
+                if is_float && !suffix.starts_with('f') {
+                    return Err(error(
+                        suffix_start,
+                        Expected::Value("f32 or f64 for a literal with a fraction or exponent"),
+                    ));
+                }
+
+                let (radix, digit) =
+                    radix.map_or((10, "a decimal digit"), |(_, radix, digit)| (radix, digit));
+                let bad_char = |allowed: &dyn Fn(char) -> bool, expected| {
+                    let offset = digits
+                        .char_indices()
+                        .find(|&(_, c)| !allowed(c))
+                        .map_or(digits_start, |(at, _)| digits_start + at);
+                    error(offset, Expected::Value(expected))
+                };
+                if is_float {
+                    let allowed = |c: char| c.is_ascii_digit() || "._eE+-".contains(c);
+                    if !["inf", "infinity", "NaN"].contains(&digits)
+                        && (cleaned.is_empty() || !digits.chars().all(allowed))
+                    {
+                        return Err(bad_char(&allowed, "a decimal number"));
+                    }
+                } else {
+                    let allowed = |c: char| c == '_' || c.is_digit(radix);
+                    if cleaned.is_empty() || !digits.chars().all(allowed) {
+                        return Err(bad_char(&allowed, digit));
+                    }
+                }
+                let number = format!("{}{}", &s[..sign_len], cleaned);
+
+                // What's left to go wrong is the value itself.
+                macro_rules! explain_as {
+                    (ints: $($int:ident),*; floats: $($float:ident),*) => {
+                        match suffix {
+                            $(stringify!($int) => $int::from_str_radix(&number, radix)
+                                .map(Primitive::explain)
+                                .map_err(|_| error(0, Expected::Value(
+                                    concat!("a value that fits in ", stringify!($int)),
+                                ))),)*
+                            $(stringify!($float) => number
+                                .parse::<$float>()
+                                .map(Primitive::explain)
+                                .map_err(|_| error(digits_start, Expected::Value("a decimal number"))),)*
+                            _ => unreachable!("every suffix is listed"),
+                        }
+                    };
+                }
+                explain_as!(
+                    ints: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize;
+                    floats: f32, f64
+                )
+            }
+        }
+    }
+}
 
 #[cfg(test)]
-mod test {}
+mod test {
+    use std::num::FpCategory;
+
+    use super::chapter_2::{
+        representation::{Cast, CastEffect, Kind, Layout, Primitive, Representation},
+        Matrix,
+    };
+    use crate::chapters::chapter_1::chapter_1::parse::{Expected, ParseError};
+
+    #[test]
+    fn matrix_transpose() {
+        // The RBE exercise: print a 2×2 matrix and its transpose.
+        let matrix = Matrix::from((1.1, 1.2, 2.1, 2.2));
+        assert_eq!("( 1.1 1.2 )\n( 2.1 2.2 )", matrix.to_string());
+        assert_eq!("( 1.1 2.1 )\n( 1.2 2.2 )", matrix.transpose().to_string());
+
+        let wide = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let tall: Matrix<3, 2> = wide.transpose();
+        assert_eq!(
+            [[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]],
+            <[[f64; 2]; 3]>::from(tall)
+        );
+        assert_eq!(wide, tall.transpose());
+    }
+
+    #[test]
+    fn matrix_display() {
+        let matrix = Matrix::new([[1.0, -20.5], [300.25, 4.0]]);
+        assert_eq!("(      1 -20.5 )\n( 300.25     4 )", matrix.to_string());
+        assert_eq!("(   1.0 -20.5 )\n( 300.2   4.0 )", format!("{:.1}", matrix));
+        assert_eq!("", Matrix::<0, 3>::zeros().to_string());
+    }
+
+    #[test]
+    fn matrix_arithmetic() {
+        let a = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let b = Matrix::new([[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]]);
+        let product: Matrix<2, 2> = a * b;
+        assert_eq!(Matrix::new([[58.0, 64.0], [139.0, 154.0]]), product);
+        assert_eq!(a, Matrix::identity() * a);
+        assert_eq!(a, a * Matrix::identity());
+        assert_eq!(Matrix::new([[2.0, 4.0, 6.0], [8.0, 10.0, 12.0]]), a * 2.0);
+        assert_eq!(a * 2.0, a + a);
+        assert_eq!(Matrix::zeros(), a - a);
+
+        let mut m = Matrix::<2, 3>::zeros();
+        m[(1, 2)] = 5.0;
+        m[(0, 0)] += 1.5;
+        assert_eq!(5.0, m[(1, 2)]);
+        assert_eq!([[1.5, 0.0, 0.0], [0.0, 0.0, 5.0]], *m.rows());
+        assert_eq!(Matrix::from_fn(|r, c| (r * 3 + c + 1) as f64), a);
+    }
+
+    #[test]
+    #[should_panic]
+    fn matrix_index_out_of_bounds() {
+        let m = Matrix::<2, 2>::identity();
+        let _ = m[(2, 0)];
+    }
+
+    #[test]
+    fn matrix_determinant_and_inverse() {
+        assert_eq!(1.0, Matrix::<0, 0>::identity().determinant());
+        assert_eq!(1.0, Matrix::<4, 4>::identity().determinant());
+        assert_eq!(-2.0, Matrix::new([[1.0, 2.0], [3.0, 4.0]]).determinant());
+        // Needs a row swap to find a non-zero pivot.
+        let swapped = Matrix::new([[0.0, 1.0, 2.0], [1.0, 0.0, 3.0], [4.0, -3.0, 8.0]]);
+        assert!((swapped.determinant() - -2.0).abs() < 1e-12);
+
+        let inverse = swapped.inverse().unwrap();
+        assert!((swapped * inverse).approx_eq(&Matrix::identity(), 1e-12));
+        assert!(inverse.approx_eq(
+            &Matrix::new([[-4.5, 7.0, -1.5], [-2.0, 4.0, -1.0], [1.5, -2.0, 0.5]]),
+            1e-12
+        ));
+
+        let singular = Matrix::new([[1.0, 2.0], [3.0, 6.0]]);
+        assert_eq!(0.0, singular.determinant());
+        assert_eq!(None, singular.inverse());
+        assert_eq!(None, Matrix::<3, 3>::zeros().inverse());
+        // Rows that differ only by rounding error are still singular.
+        let nearly = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        assert_eq!(None, nearly.inverse());
+    }
+
+    fn cast<'a>(representation: &'a Representation, target: &str) -> &'a Cast {
+        representation
+            .casts
+            .iter()
+            .find(|c| c.target == target)
+            .unwrap()
+    }
+
+    #[test]
+    fn integer_representation() {
+        let minus_five = (-5i8).explain();
+        assert_eq!("i8", minus_five.type_name);
+        assert_eq!(Kind::Signed, minus_five.kind);
+        assert_eq!("11111011", minus_five.binary);
+        assert_eq!(
+            Layout::TwosComplement {
+                magnitude: "00000101".to_owned(),
+                inverted: Some("11111010".to_owned()),
+            },
+            minus_five.layout
+        );
+        // Every other primitive type, but not `i8` itself.
+        assert_eq!(13, minus_five.casts.len());
+
+        let value = 0x1234_5678u32.explain();
+        assert_eq!(vec![0x12, 0x34, 0x56, 0x78], value.big_endian);
+        assert_eq!(vec![0x78, 0x56, 0x34, 0x12], value.little_endian);
+        assert_eq!(Layout::Unsigned, value.layout);
+
+        let min = i128::MIN.explain();
+        assert_eq!(format!("1{}", "0".repeat(127)), min.binary);
+        assert_eq!(
+            Layout::TwosComplement {
+                magnitude: format!("1{}", "0".repeat(127)),
+                inverted: Some(format!("0{}", "1".repeat(127))),
+            },
+            min.layout
+        );
+
+        assert_eq!(
+            "\
+-5: i8, 8 bits
+  binary:        11111011
+  negative:      magnitude 00000101, inverted 11111010, plus one 11111011
+  big-endian:    fb
+  little-endian: fb
+  casts:
+    as i16   -5 (exact)
+    as i32   -5 (exact)
+    as i64   -5 (exact)
+    as i128  -5 (exact)
+    as isize -5 (exact)
+    as u8    251 (reinterpreted)
+    as u16   65531 (sign-extended)
+    as u32   4294967291 (sign-extended)
+    as u64   18446744073709551611 (sign-extended)
+    as u128  340282366920938463463374607431768211451 (sign-extended)
+    as usize 18446744073709551611 (sign-extended)
+    as f32   -5 (exact)
+    as f64   -5 (exact)",
+            minus_five.to_string()
+        );
+    }
+
+    #[test]
+    fn float_representation() {
+        let value = (-0.15625f32).explain();
+        let Layout::Float(parts) = &value.layout else {
+            panic!("expected a float layout");
+        };
+        assert!(parts.negative);
+        assert_eq!(FpCategory::Normal, parts.class);
+        assert_eq!(124, parts.biased_exponent);
+        assert_eq!(Some(-3), parts.exponent);
+        assert_eq!(Some(1.25), parts.significand);
+        assert_eq!(vec![0xbe, 0x20, 0, 0], value.big_endian);
+
+        let classes = [
+            (f64::MIN_POSITIVE / 4.0, FpCategory::Subnormal, Some(-1022)),
+            (-0.0, FpCategory::Zero, Some(-1022)),
+            (f64::INFINITY, FpCategory::Infinite, None),
+            (f64::NAN, FpCategory::Nan, None),
+        ];
+        for (value, class, exponent) in classes {
+            let Layout::Float(parts) = value.explain().layout else {
+                panic!("expected a float layout");
+            };
+            assert_eq!(
+                (class, exponent),
+                (parts.class, parts.exponent),
+                "{}",
+                value
+            );
+        }
+        let Layout::Float(subnormal) = (f32::MIN_POSITIVE / 2.0).explain().layout else {
+            panic!("expected a float layout");
+        };
+        assert_eq!(
+            (0, Some(-126), Some(0.5)),
+            (
+                subnormal.biased_exponent,
+                subnormal.exponent,
+                subnormal.significand
+            )
+        );
+
+        assert_eq!(
+            "\
+-0.15625: f32, 32 bits
+  binary:        1 01111100 01000000000000000000000
+  class:         normal
+  sign:          1 (negative)
+  exponent:      01111100 = 124, unbiased -3
+  mantissa:      01000000000000000000000 (significand 1.25)
+  value:         -1.25 × 2^-3
+  big-endian:    be 20 00 00
+  little-endian: 00 00 20 be
+  casts:",
+            value
+                .to_string()
+                .lines()
+                .take(10)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+        let infinity = f32::NEG_INFINITY.explain().to_string();
+        assert!(infinity.contains("  class:         infinite\n"));
+        assert!(infinity.contains("  exponent:      11111111 (all ones)\n"));
+        assert!(!infinity.contains("value:"));
+    }
+
+    #[test]
+    fn cast_effects() {
+        let effect = |representation: Representation, target| {
+            let cast = cast(&representation, target);
+            (cast.result.clone(), cast.effect)
+        };
+        assert_eq!(
+            ("44".to_owned(), CastEffect::Truncated),
+            effect(300u16.explain(), "u8")
+        );
+        assert_eq!(
+            ("-1".to_owned(), CastEffect::Exact),
+            effect((-1i64).explain(), "i8")
+        );
+        assert_eq!(
+            ("-1".to_owned(), CastEffect::Reinterpreted),
+            effect(u32::MAX.explain(), "i32")
+        );
+        assert_eq!(
+            ("16777216".to_owned(), CastEffect::Rounded),
+            effect(16_777_217i32.explain(), "f32")
+        );
+        assert_eq!(
+            ("-2".to_owned(), CastEffect::TowardZero),
+            effect((-2.9f64).explain(), "i8")
+        );
+        assert_eq!(
+            ("255".to_owned(), CastEffect::Saturated),
+            effect(1e10f32.explain(), "u8")
+        );
+        assert_eq!(
+            ("0".to_owned(), CastEffect::Saturated),
+            effect((-1.0f64).explain(), "u16")
+        );
+        assert_eq!(
+            ("0".to_owned(), CastEffect::NanToZero),
+            effect(f32::NAN.explain(), "i32")
+        );
+        assert_eq!(
+            ("inf".to_owned(), CastEffect::Overflowed),
+            effect(1e300f64.explain(), "f32")
+        );
+        assert_eq!(
+            ("0.1".to_owned(), CastEffect::Rounded),
+            effect(0.1f64.explain(), "f32")
+        );
+        assert_eq!(
+            ("0.10000000149011612".to_owned(), CastEffect::Exact),
+            effect(0.1f32.explain(), "f64")
+        );
+        assert_eq!(
+            ("-1".to_owned(), CastEffect::Reinterpreted),
+            effect(u128::MAX.explain(), "i128")
+        );
+        // 2^128 - 1 rounds up past `f32::MAX`.
+        assert_eq!(
+            ("inf".to_owned(), CastEffect::Overflowed),
+            effect(u128::MAX.explain(), "f32")
+        );
+    }
+
+    #[test]
+    fn parse_literals() {
+        assert_eq!((-5i8).explain(), "-5i8".parse().unwrap());
+        assert_eq!(128u8.explain(), "0x80_u8".parse().unwrap());
+        assert_eq!((-128i8).explain(), "-0x80i8".parse().unwrap());
+        assert_eq!(5i8.explain(), "0b101i8".parse().unwrap());
+        assert_eq!(42i32.explain(), "42".parse().unwrap());
+        assert_eq!(1_000_000i32.explain(), "1_000_000".parse().unwrap());
+        assert_eq!(0.1f32.explain(), "0.1f32".parse().unwrap());
+        assert_eq!(1e300f64.explain(), "1e300".parse().unwrap());
+        assert_eq!(2.5f64.explain(), "2.5".parse().unwrap());
+        assert_eq!(7u128.explain(), "7u128".parse().unwrap());
+
+        // In hex, `f32` is digits rather than a suffix.
+        assert_eq!(7986i32.explain(), "0x1f32".parse().unwrap());
+        assert_eq!(0x1f_u8.explain(), "0x1f_u8".parse().unwrap());
+
+        let error = |offset, expected| Err(ParseError { offset, expected });
+        assert_eq!(
+            error(0, Expected::Value("a value that fits in u8")),
+            "256u8".parse::<Representation>()
+        );
+        assert_eq!(
+            error(0, Expected::Value("a value that fits in u32")),
+            "-1u32".parse::<Representation>()
+        );
+        assert_eq!(
+            error(
+                3,
+                Expected::Value("f32 or f64 for a literal with a fraction or exponent")
+            ),
+            "1.5i32".parse::<Representation>()
+        );
+        assert_eq!(
+            error(0, Expected::Value("a decimal number")),
+            "five".parse::<Representation>()
+        );
+        assert_eq!(
+            error(4, Expected::Value("a hex digit")),
+            "-0x1g".parse::<Representation>()
+        );
+        assert_eq!(
+            error(3, Expected::Value("a binary digit")),
+            "0b1f32".parse::<Representation>()
+        );
+        assert_eq!(
+            error(2, Expected::Value("a decimal digit")),
+            "12q8".parse::<Representation>()
+        );
+        assert_eq!(
+            error(2, Expected::Value("a hex digit")),
+            "0xu8".parse::<Representation>()
+        );
+        assert_eq!(
+            error(
+                0,
+                Expected::Value("a primitive literal such as -5i8 or 0.1f32")
+            ),
+            "".parse::<Representation>()
+        );
+    }
+}
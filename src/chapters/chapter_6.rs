@@ -1,35 +1,1387 @@
 pub mod chapter_6 {
-    #[derive(Debug, PartialEq)]
-    pub struct EvenNumber(pub i32);
+    #[allow(unused_imports)]
+    pub use self::refined::{
+        And, ConversionError, Even, InRange, Matches, MaxLen, NonZero, Not, Or, Pattern, Positive,
+        Predicate, Refined, RefinementError,
+    };
 
-    impl TryFrom<i32> for EvenNumber {
-        type Error = ();
+    // What used to be a hand-written newtype is now one refinement among many.
+    #[allow(dead_code)]
+    pub type EvenNumber = Refined<i32, Even>;
 
-        fn try_from(value: i32) -> Result<Self, Self::Error> {
-            if value % 2 == 0 {
-                Ok(EvenNumber(value))
-            } else {
-                Err(())
+    // The error names from before the generalisation, so callers that match on
+    // them keep compiling. Narrowing and parsing add the inner error `E`.
+    #[allow(dead_code)]
+    pub type OddNumberError = RefinementError<i32>;
+    #[allow(dead_code)]
+    pub type EvenNumberError<E> = ConversionError<i32, E>;
+
+    // A value of type `T` that is known to satisfy `P`. The only ways in are
+    // `new`, `TryFrom` and `FromStr`, all of which check, so code holding a
+    // `Refined` never has to.
+    #[allow(dead_code)]
+    pub mod refined {
+        use core::fmt;
+        use std::cmp::Ordering;
+        use std::hash::{Hash, Hasher};
+        use std::marker::PhantomData;
+        use std::num::TryFromIntError;
+        use std::ops::{Add, Deref, Mul, Sub};
+        use std::str::FromStr;
+
+        // A rule about values of `T`. Predicates are types rather than values
+        // so they can sit in `Refined`'s type, e.g. `Refined<u8, InRange<1, 12>>`.
+        pub trait Predicate<T: ?Sized> {
+            fn test(value: &T) -> bool;
+
+            // Finishes the sentence "the value must be ...".
+            fn describe() -> String;
+
+            // The description of the part of the predicate `value` fails.
+            // Combinators override this to point at the exact culprit.
+            fn check(value: &T) -> Result<(), String> {
+                if Self::test(value) {
+                    Ok(())
+                } else {
+                    Err(Self::describe())
+                }
+            }
+
+            // What `Debug` calls a `Refined` with this predicate, so a refined
+            // type can print like the newtype it stands for.
+            fn debug_name() -> &'static str {
+                "Refined"
+            }
+        }
+
+        pub struct Even;
+        pub struct Positive;
+        pub struct NonZero;
+        pub struct InRange<const MIN: i128, const MAX: i128>;
+        // Counts characters, not bytes.
+        pub struct MaxLen<const N: usize>;
+        pub struct Matches<P>(PhantomData<P>);
+        pub struct And<A, B>(PhantomData<(A, B)>);
+        pub struct Or<A, B>(PhantomData<(A, B)>);
+        pub struct Not<A>(PhantomData<A>);
+
+        impl<T: Copy + Into<i128>> Predicate<T> for Even {
+            fn test(value: &T) -> bool {
+                (*value).into() % 2 == 0
+            }
+
+            fn describe() -> String {
+                "even".to_owned()
+            }
+
+            fn debug_name() -> &'static str {
+                "EvenNumber"
+            }
+        }
+
+        // Zero is `T::default()` for every number type, floats included.
+        impl<T: Default + PartialOrd> Predicate<T> for Positive {
+            fn test(value: &T) -> bool {
+                *value > T::default()
+            }
+
+            fn describe() -> String {
+                "positive".to_owned()
+            }
+        }
+
+        impl<T: Default + PartialEq> Predicate<T> for NonZero {
+            fn test(value: &T) -> bool {
+                *value != T::default()
+            }
+
+            fn describe() -> String {
+                "non-zero".to_owned()
+            }
+        }
+
+        impl<T: Copy + Into<i128>, const MIN: i128, const MAX: i128> Predicate<T> for InRange<MIN, MAX> {
+            fn test(value: &T) -> bool {
+                (MIN..=MAX).contains(&(*value).into())
+            }
+
+            fn describe() -> String {
+                format!("between {} and {}", MIN, MAX)
+            }
+        }
+
+        impl<T: AsRef<str> + ?Sized, const N: usize> Predicate<T> for MaxLen<N> {
+            fn test(value: &T) -> bool {
+                value.as_ref().chars().count() <= N
+            }
+
+            fn describe() -> String {
+                format!("at most {} characters long", N)
+            }
+        }
+
+        impl<T: AsRef<str> + ?Sized, P: Pattern> Predicate<T> for Matches<P> {
+            fn test(value: &T) -> bool {
+                let text: Vec<char> = value.as_ref().chars().collect();
+                matches(&compile(P::PATTERN), &text)
+            }
+
+            fn describe() -> String {
+                format!("made of {}", P::PATTERN)
+            }
+        }
+
+        // Reports whichever side fails first.
+        impl<T: ?Sized, A: Predicate<T>, B: Predicate<T>> Predicate<T> for And<A, B> {
+            fn test(value: &T) -> bool {
+                A::test(value) && B::test(value)
+            }
+
+            fn describe() -> String {
+                format!("{} and {}", A::describe(), B::describe())
+            }
+
+            fn check(value: &T) -> Result<(), String> {
+                A::check(value)?;
+                B::check(value)
+            }
+        }
+
+        impl<T: ?Sized, A: Predicate<T>, B: Predicate<T>> Predicate<T> for Or<A, B> {
+            fn test(value: &T) -> bool {
+                A::test(value) || B::test(value)
+            }
+
+            fn describe() -> String {
+                format!("{} or {}", A::describe(), B::describe())
+            }
+        }
+
+        impl<T: ?Sized, A: Predicate<T>> Predicate<T> for Not<A> {
+            fn test(value: &T) -> bool {
+                !A::test(value)
+            }
+
+            fn describe() -> String {
+                format!("anything but {}", A::describe())
+            }
+        }
+
+        // A whole-string pattern for `Matches`, such as `[a-z_][a-z0-9_]*`:
+        // literal characters, `.`, classes like `[a-z]` or `[^,]`, each
+        // optionally followed by `?`, `*` or `+`, and `\` to escape.
+        pub trait Pattern {
+            const PATTERN: &'static str;
+        }
+
+        enum Atom {
+            Char(char),
+            // `.` is an empty, negated class.
+            Class {
+                negated: bool,
+                ranges: Vec<(char, char)>,
+            },
+        }
+
+        impl Atom {
+            fn accepts(&self, c: char) -> bool {
+                match self {
+                    Atom::Char(expected) => c == *expected,
+                    Atom::Class { negated, ranges } => {
+                        ranges.iter().any(|&(low, high)| (low..=high).contains(&c)) != *negated
+                    }
+                }
+            }
+        }
+
+        struct Item {
+            atom: Atom,
+            min: usize,
+            max: usize,
+        }
+
+        // Patterns are fixed in the source, so a malformed one is a bug and
+        // panics rather than failing every value.
+        fn compile(pattern: &str) -> Vec<Item> {
+            let malformed = || -> ! { panic!("malformed pattern `{}`", pattern) };
+            let mut chars = pattern.chars().peekable();
+            let mut items = Vec::new();
+
+            while let Some(c) = chars.next() {
+                let atom = match c {
+                    '.' => Atom::Class {
+                        negated: true,
+                        ranges: Vec::new(),
+                    },
+                    '\\' => Atom::Char(chars.next().unwrap_or_else(|| malformed())),
+                    '[' => {
+                        let negated = chars.next_if_eq(&'^').is_some();
+                        let mut ranges = Vec::new();
+                        loop {
+                            let low = match chars.next() {
+                                Some(']') if !ranges.is_empty() => break,
+                                Some('\\') => chars.next().unwrap_or_else(|| malformed()),
+                                Some(c) => c,
+                                None => malformed(),
+                            };
+                            let high = if chars.next_if_eq(&'-').is_some() {
+                                match chars.next() {
+                                    Some(']') | None => malformed(),
+                                    Some(high) if high < low => malformed(),
+                                    Some(high) => high,
+                                }
+                            } else {
+                                low
+                            };
+                            ranges.push((low, high));
+                        }
+                        Atom::Class { negated, ranges }
+                    }
+                    '?' | '*' | '+' | ']' => malformed(),
+                    c => Atom::Char(c),
+                };
+                let (min, max) = match chars.next_if(|c| matches!(c, '?' | '*' | '+')) {
+                    Some('?') => (0, 1),
+                    Some('*') => (0, usize::MAX),
+                    Some(_) => (1, usize::MAX),
+                    None => (1, 1),
+                };
+                items.push(Item { atom, min, max });
+            }
+            items
+        }
+
+        // Greedy with backtracking; patterns and values here are short.
+        fn matches(items: &[Item], text: &[char]) -> bool {
+            let Some((item, rest)) = items.split_first() else {
+                return text.is_empty();
+            };
+            let run = text
+                .iter()
+                .take(item.max)
+                .take_while(|&&c| item.atom.accepts(c))
+                .count();
+            (item.min..=run)
+                .rev()
+                .any(|taken| matches(rest, &text[taken..]))
+        }
+
+        // The value that was turned away and the predicate, or part of one,
+        // that did it.
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct RefinementError<T> {
+            pub value: T,
+            pub expected: String,
+        }
+
+        impl<T: fmt::Debug> fmt::Display for RefinementError<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{:?} must be {}", self.value, self.expected)
+            }
+        }
+
+        impl<T: fmt::Debug> std::error::Error for RefinementError<T> {}
+
+        // Parsing or narrowing can fail before the predicate is checked.
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum ConversionError<T, E> {
+            Inner(E),
+            Refinement(RefinementError<T>),
+        }
+
+        impl<T: fmt::Debug, E: fmt::Display> fmt::Display for ConversionError<T, E> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    ConversionError::Inner(error) => error.fmt(f),
+                    ConversionError::Refinement(error) => error.fmt(f),
+                }
+            }
+        }
+
+        impl<T, E> std::error::Error for ConversionError<T, E>
+        where
+            T: fmt::Debug + 'static,
+            E: std::error::Error + 'static,
+        {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                match self {
+                    ConversionError::Inner(error) => Some(error),
+                    ConversionError::Refinement(error) => Some(error),
+                }
+            }
+        }
+
+        impl<T, E> From<RefinementError<T>> for ConversionError<T, E> {
+            fn from(error: RefinementError<T>) -> ConversionError<T, E> {
+                ConversionError::Refinement(error)
+            }
+        }
+
+        // `fn() -> P` keeps `Refined` `Send`, `Sync` and `Copy` whatever the
+        // predicate type is.
+        pub struct Refined<T, P> {
+            value: T,
+            predicate: PhantomData<fn() -> P>,
+        }
+
+        impl<T, P: Predicate<T>> Refined<T, P> {
+            pub fn new(value: T) -> Result<Refined<T, P>, RefinementError<T>> {
+                match P::check(&value) {
+                    Ok(()) => Ok(Refined::unchecked(value)),
+                    Err(expected) => Err(RefinementError { value, expected }),
+                }
+            }
+        }
+
+        impl<T, P> Refined<T, P> {
+            // Only for values the predicate is already known to accept.
+            pub(super) fn unchecked(value: T) -> Refined<T, P> {
+                Refined {
+                    value,
+                    predicate: PhantomData,
+                }
+            }
+
+            pub fn get(&self) -> &T {
+                &self.value
+            }
+
+            pub fn into_inner(self) -> T {
+                self.value
+            }
+        }
+
+        impl<T, P> Deref for Refined<T, P> {
+            type Target = T;
+
+            fn deref(&self) -> &T {
+                &self.value
+            }
+        }
+
+        impl<T: FromStr, P: Predicate<T>> FromStr for Refined<T, P> {
+            type Err = ConversionError<T, T::Err>;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let value = s.parse().map_err(ConversionError::Inner)?;
+                Ok(Refined::new(value)?)
+            }
+        }
+
+        // A blanket `TryFrom<T>` would clash with the standard library's, so
+        // it is spelled out for each inner type.
+        macro_rules! try_from_inner {
+            ($($t:ty),*) => {
+                $(impl<P: Predicate<$t>> TryFrom<$t> for Refined<$t, P> {
+                    type Error = RefinementError<$t>;
+
+                    fn try_from(value: $t) -> Result<Self, Self::Error> {
+                        Refined::new(value)
+                    }
+                })*
+            };
+        }
+
+        try_from_inner!(
+            i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, char, bool,
+            String
+        );
+
+        impl<P: Predicate<String>> TryFrom<&str> for Refined<String, P> {
+            type Error = RefinementError<String>;
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                Refined::new(value.to_owned())
+            }
+        }
+
+        // So an `i32` refinement such as `EvenNumber` still takes any integer:
+        // narrower ones always fit, wider ones might not.
+        macro_rules! try_from_narrow {
+            ($($t:ty),*) => {
+                $(impl<P: Predicate<i32>> TryFrom<$t> for Refined<i32, P> {
+                    type Error = RefinementError<i32>;
+
+                    fn try_from(value: $t) -> Result<Self, Self::Error> {
+                        Refined::new(i32::from(value))
+                    }
+                })*
+            };
+        }
+
+        try_from_narrow!(i8, i16, u8, u16);
+
+        macro_rules! try_from_wide {
+            ($($t:ty),*) => {
+                $(impl<P: Predicate<i32>> TryFrom<$t> for Refined<i32, P> {
+                    type Error = ConversionError<i32, TryFromIntError>;
+
+                    fn try_from(value: $t) -> Result<Self, Self::Error> {
+                        let value = i32::try_from(value).map_err(ConversionError::Inner)?;
+                        Ok(Refined::new(value)?)
+                    }
+                })*
+            };
+        }
+
+        try_from_wide!(i64, i128, isize, u32, u64, u128, usize);
+
+        // The usual traits, written out so they don't demand anything of `P`.
+        impl<T: Clone, P> Clone for Refined<T, P> {
+            fn clone(&self) -> Self {
+                Refined::unchecked(self.value.clone())
+            }
+        }
+
+        impl<T: Copy, P> Copy for Refined<T, P> {}
+
+        impl<T: fmt::Debug, P: Predicate<T>> fmt::Debug for Refined<T, P> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(P::debug_name()).field(&self.value).finish()
+            }
+        }
+
+        impl<T: fmt::Display, P> fmt::Display for Refined<T, P> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.value.fmt(f)
+            }
+        }
+
+        impl<T: PartialEq, P> PartialEq for Refined<T, P> {
+            fn eq(&self, other: &Self) -> bool {
+                self.value == other.value
+            }
+        }
+
+        impl<T: Eq, P> Eq for Refined<T, P> {}
+
+        impl<T: PartialOrd, P> PartialOrd for Refined<T, P> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.value.partial_cmp(&other.value)
+            }
+        }
+
+        impl<T: Ord, P> Ord for Refined<T, P> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.value.cmp(&other.value)
+            }
+        }
+
+        impl<T: Hash, P> Hash for Refined<T, P> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.value.hash(state)
+            }
+        }
+
+        // Sums, differences and products of even numbers are even, so these
+        // keep the type. Like `i32`'s own operators they panic on overflow in
+        // debug builds; wrapping in release can't make the result odd either.
+        impl Add for Refined<i32, Even> {
+            type Output = Refined<i32, Even>;
+
+            fn add(self, rhs: Self) -> Self {
+                Refined::unchecked(self.value + rhs.value)
+            }
+        }
+
+        impl Sub for Refined<i32, Even> {
+            type Output = Refined<i32, Even>;
+
+            fn sub(self, rhs: Self) -> Self {
+                Refined::unchecked(self.value - rhs.value)
+            }
+        }
+
+        impl Mul for Refined<i32, Even> {
+            type Output = Refined<i32, Even>;
+
+            fn mul(self, rhs: Self) -> Self {
+                Refined::unchecked(self.value * rhs.value)
+            }
+        }
+
+        // An even number times anything is still even.
+        impl Mul<i32> for Refined<i32, Even> {
+            type Output = Refined<i32, Even>;
+
+            fn mul(self, rhs: i32) -> Self {
+                Refined::unchecked(self.value * rhs)
+            }
+        }
+
+        impl Refined<i32, Even> {
+            pub fn checked_add(self, rhs: Self) -> Option<Self> {
+                self.value.checked_add(rhs.value).map(Refined::unchecked)
+            }
+
+            pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+                self.value.checked_sub(rhs.value).map(Refined::unchecked)
+            }
+
+            pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+                self.value.checked_mul(rhs.value).map(Refined::unchecked)
+            }
+
+            // Exact, since the value is even. The half itself may be odd,
+            // hence the plain `i32`.
+            pub fn halve(self) -> i32 {
+                self.value / 2
             }
         }
     }
+
+    // `Even` and `Odd` integers whose arithmetic carries parity in the types:
+    // the sum of two `Odd`s is an `Even`, so it can go straight into a
+    // function that takes one, with nothing checked at runtime. Only
+    // conversions from plain integers look at the value.
+    //
+    // The compile-fail examples below pin down what must not type-check;
+    // `cargo test` runs them as doctests against the library.
+    #[allow(dead_code)]
+    pub mod parity {
+        use core::fmt;
+        use std::ops::{Add, Mul, Neg, Sub};
+
+        use super::refined::Even as EvenPredicate;
+        use super::refined::{Refined, RefinementError};
+        use super::EvenNumber;
+
+        /// An even `i32`.
+        ///
+        /// Adding two odd numbers needs no check:
+        ///
+        /// ```
+        /// # use rust_by_examples::chapters::chapter_6::chapter_6::parity::{Even, Odd};
+        /// let two: Even = Odd::ONE + Odd::ONE;
+        /// assert_eq!(1, two.halve());
+        /// ```
+        ///
+        /// but their sum is not `Odd`:
+        ///
+        /// ```compile_fail,E0308
+        /// # use rust_by_examples::chapters::chapter_6::chapter_6::parity::{Even, Odd};
+        /// let two: Odd = Odd::ONE + Odd::ONE;
+        /// ```
+        ///
+        /// Nor can the tuple field be used to skip the check:
+        ///
+        /// ```compile_fail,E0423
+        /// # use rust_by_examples::chapters::chapter_6::chapter_6::parity::{Even, Odd};
+        /// let three = Even(3);
+        /// ```
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct Even(i32);
+
+        /// An odd `i32`.
+        ///
+        /// An `Odd` isn't accepted where an `Even` is expected:
+        ///
+        /// ```compile_fail,E0308
+        /// # use rust_by_examples::chapters::chapter_6::chapter_6::parity::{Even, Odd};
+        /// fn halve(even: Even) -> i32 { even.halve() }
+        /// halve(Odd::ONE);
+        /// ```
+        ///
+        /// and a product with an `Even` factor is never `Odd`:
+        ///
+        /// ```compile_fail,E0308
+        /// # use rust_by_examples::chapters::chapter_6::chapter_6::parity::{Even, Odd};
+        /// let product: Odd = Odd::ONE * Even::ZERO;
+        /// ```
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct Odd(i32);
+
+        // A plain integer sorted by parity, which never fails.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Parity {
+            Even(Even),
+            Odd(Odd),
+        }
+
+        impl Even {
+            pub const ZERO: Even = Even(0);
+            pub const TWO: Even = Even(2);
+
+            pub fn get(self) -> i32 {
+                self.0
+            }
+
+            // Exact; the half may be either parity, hence the plain `i32`.
+            pub fn halve(self) -> i32 {
+                self.0 / 2
+            }
+        }
+
+        impl Odd {
+            pub const ONE: Odd = Odd(1);
+
+            pub fn get(self) -> i32 {
+                self.0
+            }
+        }
+
+        impl From<i32> for Parity {
+            fn from(value: i32) -> Parity {
+                if value % 2 == 0 {
+                    Parity::Even(Even(value))
+                } else {
+                    Parity::Odd(Odd(value))
+                }
+            }
+        }
+
+        impl TryFrom<i32> for Even {
+            type Error = RefinementError<i32>;
+
+            fn try_from(value: i32) -> Result<Even, Self::Error> {
+                match Parity::from(value) {
+                    Parity::Even(even) => Ok(even),
+                    Parity::Odd(_) => Err(RefinementError {
+                        value,
+                        expected: "even".to_owned(),
+                    }),
+                }
+            }
+        }
+
+        impl TryFrom<i32> for Odd {
+            type Error = RefinementError<i32>;
+
+            fn try_from(value: i32) -> Result<Odd, Self::Error> {
+                match Parity::from(value) {
+                    Parity::Odd(odd) => Ok(odd),
+                    Parity::Even(_) => Err(RefinementError {
+                        value,
+                        expected: "odd".to_owned(),
+                    }),
+                }
+            }
+        }
+
+        impl From<Even> for i32 {
+            fn from(even: Even) -> i32 {
+                even.0
+            }
+        }
+
+        impl From<Odd> for i32 {
+            fn from(odd: Odd) -> i32 {
+                odd.0
+            }
+        }
+
+        // `EvenNumber` has already checked, so both directions are free.
+        impl From<EvenNumber> for Even {
+            fn from(even: EvenNumber) -> Even {
+                Even(even.into_inner())
+            }
+        }
+
+        impl From<Even> for EvenNumber {
+            fn from(even: Even) -> EvenNumber {
+                Refined::<i32, EvenPredicate>::unchecked(even.0)
+            }
+        }
+
+        impl fmt::Display for Even {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl fmt::Display for Odd {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        // One line of the parity table per impl. As with `i32`, overflow
+        // panics in debug builds; wrapping would keep the parity anyway.
+        macro_rules! parity_op {
+            ($trait:ident, $method:ident, $op:tt: $($lhs:ident, $rhs:ident => $out:ident;)*) => {
+                $(impl $trait<$rhs> for $lhs {
+                    type Output = $out;
+
+                    fn $method(self, rhs: $rhs) -> $out {
+                        $out(self.0 $op rhs.0)
+                    }
+                })*
+            };
+        }
+
+        parity_op!(Add, add, +:
+            Even, Even => Even;
+            Even, Odd => Odd;
+            Odd, Even => Odd;
+            Odd, Odd => Even;
+        );
+
+        parity_op!(Sub, sub, -:
+            Even, Even => Even;
+            Even, Odd => Odd;
+            Odd, Even => Odd;
+            Odd, Odd => Even;
+        );
+
+        parity_op!(Mul, mul, *:
+            Even, Even => Even;
+            Even, Odd => Even;
+            Odd, Even => Even;
+            Odd, Odd => Odd;
+        );
+
+        impl Neg for Even {
+            type Output = Even;
+
+            fn neg(self) -> Even {
+                Even(-self.0)
+            }
+        }
+
+        impl Neg for Odd {
+            type Output = Odd;
+
+            fn neg(self) -> Odd {
+                Odd(-self.0)
+            }
+        }
+    }
+
+    // Arithmetic modulo `M`, the generalisation of the parity check above
+    // (which is arithmetic modulo 2). Everything goes through `u128`, so even
+    // a modulus near `u64::MAX` can't overflow.
+    #[allow(dead_code)]
+    pub mod modular {
+        use core::fmt;
+        use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct ModInt<const M: u64>(u64);
+
+        // Only values coprime with the modulus can be inverted.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct NoInverse {
+            pub value: u64,
+            pub modulus: u64,
+            pub gcd: u64,
+        }
+
+        impl fmt::Display for NoInverse {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "{} has no inverse mod {}: they share the factor {}",
+                    self.value, self.modulus, self.gcd
+                )
+            }
+        }
+
+        impl std::error::Error for NoInverse {}
+
+        impl<const M: u64> ModInt<M> {
+            // Evaluated per `M`, so code that builds a `ModInt<0>` fails to
+            // compile. Every way in, the constants included, refers to it.
+            const NONZERO_MODULUS: () = assert!(M > 0, "the modulus must be positive");
+
+            pub const ZERO: ModInt<M> = {
+                let () = Self::NONZERO_MODULUS;
+                ModInt(0)
+            };
+            pub const ONE: ModInt<M> = {
+                let () = Self::NONZERO_MODULUS;
+                ModInt(1 % M)
+            };
+
+            pub fn new(value: u64) -> ModInt<M> {
+                let () = Self::NONZERO_MODULUS;
+                ModInt(value % M)
+            }
+
+            // Negative values wrap around, so -1 is `M - 1`.
+            pub fn from_signed(value: i64) -> ModInt<M> {
+                let () = Self::NONZERO_MODULUS;
+                ModInt(i128::from(value).rem_euclid(i128::from(M)) as u64)
+            }
+
+            pub fn value(self) -> u64 {
+                self.0
+            }
+
+            pub const fn modulus() -> u64 {
+                M
+            }
+
+            // Square-and-multiply: `exponent`'s bits, not its size, set the
+            // number of steps.
+            pub fn pow(self, mut exponent: u64) -> ModInt<M> {
+                let (mut base, mut result) = (self, ModInt::ONE);
+                while exponent > 0 {
+                    if exponent & 1 == 1 {
+                        result *= base;
+                    }
+                    base *= base;
+                    exponent >>= 1;
+                }
+                result
+            }
+
+            pub fn inverse(self) -> Result<ModInt<M>, NoInverse> {
+                let (gcd, x, _) = extended_gcd(self.0, M);
+                if gcd != 1 {
+                    return Err(NoInverse {
+                        value: self.0,
+                        modulus: M,
+                        gcd,
+                    });
+                }
+                Ok(ModInt(x.rem_euclid(i128::from(M)) as u64))
+            }
+
+            pub fn checked_div(self, rhs: ModInt<M>) -> Result<ModInt<M>, NoInverse> {
+                Ok(self * rhs.inverse()?)
+            }
+
+            pub fn residue(self) -> Residue {
+                Residue {
+                    value: self.0,
+                    modulus: M,
+                }
+            }
+        }
+
+        // Returns `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`. The
+        // coefficients stay below `max(a, b)` in size, so `i128` holds them.
+        fn extended_gcd(a: u64, b: u64) -> (u64, i128, i128) {
+            let (mut old_r, mut r) = (i128::from(a), i128::from(b));
+            let (mut old_x, mut x) = (1, 0);
+            let (mut old_y, mut y) = (0, 1);
+            while r != 0 {
+                let quotient = old_r / r;
+                (old_r, r) = (r, old_r - quotient * r);
+                (old_x, x) = (x, old_x - quotient * x);
+                (old_y, y) = (y, old_y - quotient * y);
+            }
+            (old_r as u64, old_x, old_y)
+        }
+
+        impl<const M: u64> From<u64> for ModInt<M> {
+            fn from(value: u64) -> ModInt<M> {
+                ModInt::new(value)
+            }
+        }
+
+        impl<const M: u64> From<i64> for ModInt<M> {
+            fn from(value: i64) -> ModInt<M> {
+                ModInt::from_signed(value)
+            }
+        }
+
+        impl<const M: u64> fmt::Display for ModInt<M> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} (mod {})", self.0, M)
+            }
+        }
+
+        impl<const M: u64> Add for ModInt<M> {
+            type Output = ModInt<M>;
+
+            fn add(self, rhs: ModInt<M>) -> ModInt<M> {
+                ModInt(((u128::from(self.0) + u128::from(rhs.0)) % u128::from(M)) as u64)
+            }
+        }
+
+        impl<const M: u64> Sub for ModInt<M> {
+            type Output = ModInt<M>;
+
+            fn sub(self, rhs: ModInt<M>) -> ModInt<M> {
+                self + -rhs
+            }
+        }
+
+        impl<const M: u64> Mul for ModInt<M> {
+            type Output = ModInt<M>;
+
+            fn mul(self, rhs: ModInt<M>) -> ModInt<M> {
+                ModInt(((u128::from(self.0) * u128::from(rhs.0)) % u128::from(M)) as u64)
+            }
+        }
+
+        // Panics when `rhs` has no inverse, much as integer division by zero
+        // does; `checked_div` reports it instead.
+        impl<const M: u64> Div for ModInt<M> {
+            type Output = ModInt<M>;
+
+            fn div(self, rhs: ModInt<M>) -> ModInt<M> {
+                match self.checked_div(rhs) {
+                    Ok(quotient) => quotient,
+                    Err(error) => panic!("{}", error),
+                }
+            }
+        }
+
+        impl<const M: u64> Neg for ModInt<M> {
+            type Output = ModInt<M>;
+
+            fn neg(self) -> ModInt<M> {
+                if self.0 == 0 {
+                    self
+                } else {
+                    ModInt(M - self.0)
+                }
+            }
+        }
+
+        macro_rules! assign_ops {
+            ($($trait:ident, $method:ident, $op:tt;)*) => {
+                $(impl<const M: u64> $trait for ModInt<M> {
+                    fn $method(&mut self, rhs: ModInt<M>) {
+                        *self = *self $op rhs;
+                    }
+                })*
+            };
+        }
+
+        assign_ops! {
+            AddAssign, add_assign, +;
+            SubAssign, sub_assign, -;
+            MulAssign, mul_assign, *;
+            DivAssign, div_assign, /;
+        }
+
+        // A residue whose modulus is only known at runtime, which is what
+        // combining residues gives: the combined modulus isn't something a
+        // const generic can compute on stable Rust. The fields are private so
+        // the modulus is never zero.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct Residue {
+            value: u64,
+            modulus: u64,
+        }
+
+        impl fmt::Display for Residue {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} (mod {})", self.value, self.modulus)
+            }
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum CrtError {
+            // Moduli sharing a factor must agree modulo it, and these don't.
+            Incompatible(Residue, Residue),
+            // The combined modulus doesn't fit in a `u64`.
+            Overflow,
+            Empty,
+        }
+
+        impl fmt::Display for CrtError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    CrtError::Incompatible(a, b) => write!(f, "no number is both {} and {}", a, b),
+                    CrtError::Overflow => write!(f, "the combined modulus overflows u64"),
+                    CrtError::Empty => write!(f, "no residues to combine"),
+                }
+            }
+        }
+
+        impl std::error::Error for CrtError {}
+
+        impl Residue {
+            // `None` for a modulus of 0, which has no residues.
+            pub fn new(value: u64, modulus: u64) -> Option<Residue> {
+                (modulus != 0).then(|| Residue {
+                    value: value % modulus,
+                    modulus,
+                })
+            }
+
+            pub fn value(self) -> u64 {
+                self.value
+            }
+
+            pub fn modulus(self) -> u64 {
+                self.modulus
+            }
+
+            // The Chinese Remainder Theorem: the one residue, modulo the lcm
+            // of both moduli, that agrees with `self` and `other`. Moduli
+            // need not be coprime as long as the residues are consistent.
+            pub fn combine(self, other: Residue) -> Result<Residue, CrtError> {
+                // m * x + n * y = g, so x inverts m / g modulo n / g.
+                let (g, x, _) = extended_gcd(self.modulus, other.modulus);
+                let (m, n, g) = (
+                    u128::from(self.modulus),
+                    u128::from(other.modulus),
+                    u128::from(g),
+                );
+                let (a, b) = (u128::from(self.value) % m, u128::from(other.value) % n);
+
+                let difference = (b + n - a % n) % n;
+                if difference % g != 0 {
+                    return Err(CrtError::Incompatible(self, other));
+                }
+                let lcm = m / g * n;
+                if lcm > u128::from(u64::MAX) {
+                    return Err(CrtError::Overflow);
+                }
+
+                // The answer is a + m * t with m * t = b - a (mod n). Both
+                // factors of t are below n / g, so nothing here overflows.
+                let step = n / g;
+                let inverse = x.rem_euclid(step as i128) as u128;
+                let t = difference / g * inverse % step;
+                Ok(Residue {
+                    value: ((a + m * t) % lcm) as u64,
+                    modulus: lcm as u64,
+                })
+            }
+        }
+
+        // Folds any number of residues into one.
+        pub fn crt(residues: &[Residue]) -> Result<Residue, CrtError> {
+            let (first, rest) = residues.split_first().ok_or(CrtError::Empty)?;
+            rest.iter()
+                .try_fold(*first, |combined, residue| combined.combine(*residue))
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::chapters::chapter_6::chapter_6::EvenNumber;
+    use std::num::IntErrorKind;
+
+    use crate::chapters::chapter_6::chapter_6::modular::{
+        crt, CrtError, ModInt, NoInverse, Residue,
+    };
+    use crate::chapters::chapter_6::chapter_6::parity::{self, Odd, Parity};
+    use crate::chapters::chapter_6::chapter_6::{
+        And, ConversionError, Even, EvenNumber, EvenNumberError, InRange, Matches, MaxLen, NonZero,
+        Not, OddNumberError, Or, Pattern, Positive, Refined, RefinementError,
+    };
+
+    fn even(value: i32) -> EvenNumber {
+        EvenNumber::new(value).unwrap()
+    }
+
+    fn odd(value: i32) -> OddNumberError {
+        RefinementError {
+            value,
+            expected: "even".to_owned(),
+        }
+    }
 
     #[test]
     fn try_from_test() {
         // Try from
-        assert_eq!(EvenNumber::try_from(8), Ok(EvenNumber(8)));
-        assert_eq!(EvenNumber::try_from(5), Err(()));
+        assert_eq!(EvenNumber::try_from(8), Ok(even(8)));
+        assert_eq!(EvenNumber::try_from(5), Err(odd(5)));
 
         // Try into
 
-        let result: Result<EvenNumber, ()> = 8i32.try_into();
-        assert_eq!(result, Ok(EvenNumber(8)));
-        let result: Result<EvenNumber, ()> = 5i32.try_into();
-        assert_eq!(result, Err(()));
+        let result: Result<EvenNumber, OddNumberError> = 8i32.try_into();
+        assert_eq!(result, Ok(even(8)));
+        let result: Result<EvenNumber, OddNumberError> = 5i32.try_into();
+        assert_eq!(result, Err(odd(5)));
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(EvenNumber::try_from(-8i8), Ok(even(-8)));
+        assert_eq!(EvenNumber::try_from(65535u16), Err(odd(65535)));
+        assert_eq!(EvenNumber::try_from(1u64 << 20), Ok(even(1 << 20)));
+        assert_eq!(
+            EvenNumber::try_from(7usize),
+            Err(EvenNumberError::Refinement(odd(7)))
+        );
+        assert!(matches!(
+            EvenNumber::try_from(1i64 << 40),
+            Err(ConversionError::Inner(_))
+        ));
+        assert!(matches!(
+            EvenNumber::try_from(u128::MAX),
+            Err(ConversionError::Inner(_))
+        ));
+
+        assert_eq!("-42".parse(), Ok(even(-42)));
+        assert_eq!(
+            "43".parse::<EvenNumber>(),
+            Err(ConversionError::Refinement(odd(43)))
+        );
+        assert!(matches!(
+            "forty".parse::<EvenNumber>(),
+            Err(ConversionError::Inner(error)) if *error.kind() == IntErrorKind::InvalidDigit
+        ));
+
+        assert_eq!("5 must be even", odd(5).to_string());
+        assert_eq!("EvenNumber(8)", format!("{:?}", even(8)));
+        assert_eq!(
+            "Refined(3)",
+            format!("{:?}", Refined::<i32, Positive>::new(3).unwrap())
+        );
+        assert_eq!(
+            "43 must be even",
+            "43".parse::<EvenNumber>().unwrap_err().to_string()
+        );
+
+        // `Deref` gives the inner value's methods; `into_inner` gives it back.
+        assert_eq!(12, even(-12).abs());
+        assert_eq!(-12, even(-12).into_inner());
+        assert_eq!("-12", even(-12).to_string());
+    }
+
+    #[test]
+    fn parity_preserving_arithmetic() {
+        let (a, b) = (even(10), even(-4));
+        assert_eq!(even(6), a + b);
+        assert_eq!(even(14), a - b);
+        assert_eq!(even(-40), a * b);
+        assert_eq!(even(30), a * 3);
+        assert_eq!(5, a.halve());
+        assert_eq!(-2, b.halve());
+
+        let big = even(i32::MAX - 1);
+        assert_eq!(None, big.checked_add(even(2)));
+        assert_eq!(Some(even(i32::MAX - 1)), big.checked_add(even(0)));
+        assert_eq!(None, even(i32::MIN).checked_sub(even(2)));
+        assert_eq!(None, big.checked_mul(even(2)));
+        assert_eq!(Some(even(-20)), a.checked_mul(even(-2)));
+    }
+
+    struct Username;
+
+    impl Pattern for Username {
+        const PATTERN: &'static str = "[a-z_][a-z0-9_]*";
+    }
+
+    struct Version;
+
+    impl Pattern for Version {
+        const PATTERN: &'static str = "v?[0-9]+\\.[0-9]+-?[^ .]*";
+    }
+
+    struct Initials;
+
+    impl Pattern for Initials {
+        const PATTERN: &'static str = "[A-Z]\\..?";
+    }
+
+    #[test]
+    fn refinements() {
+        type Month = Refined<u8, InRange<1, 12>>;
+        type Divisor = Refined<f64, NonZero>;
+        type Name = Refined<String, And<MaxLen<8>, Matches<Username>>>;
+
+        assert_eq!(12, *Month::try_from(12).unwrap());
+        assert_eq!(
+            "13 must be between 1 and 12",
+            Month::try_from(13).unwrap_err().to_string()
+        );
+        assert_eq!(
+            "0.0 must be non-zero",
+            "0".parse::<Divisor>().unwrap_err().to_string()
+        );
+        assert_eq!(
+            "-1.5 must be positive",
+            Refined::<f64, Positive>::new(-1.5).unwrap_err().to_string()
+        );
+
+        // `And` names the half that failed.
+        assert_eq!("zonin_42", Name::try_from("zonin_42").unwrap().as_str());
+        assert_eq!(
+            "\"zonincodes\" must be at most 8 characters long",
+            Name::try_from("zonincodes").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "\"9lives\" must be made of [a-z_][a-z0-9_]*",
+            Name::try_from("9lives").unwrap_err().to_string()
+        );
+
+        type OddOrZero = Refined<i64, Or<Not<Even>, Not<NonZero>>>;
+        assert!(OddOrZero::new(3).is_ok());
+        assert!(OddOrZero::new(0).is_ok());
+        assert_eq!(
+            "4 must be anything but even or anything but non-zero",
+            OddOrZero::new(4).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn patterns() {
+        type Tag = Refined<String, Matches<Version>>;
+        type Short = Refined<&'static str, Matches<Initials>>;
+
+        for accepted in ["1.0", "v12.04", "1.2-rc1", "1.2beta"] {
+            assert!(Tag::new(accepted.to_owned()).is_ok(), "{}", accepted);
+        }
+        for rejected in ["", "v", "1.", "1x2", "v1.2 ", "1.2-rc 1", "1.2.3"] {
+            assert!(Tag::new(rejected.to_owned()).is_err(), "{}", rejected);
+        }
+
+        assert!(Short::new("Z.").is_ok());
+        assert!(Short::new("Z.!").is_ok());
+        assert!(Short::new("Zz").is_err());
+    }
+
+    // Only compiles because the parity of each result is known.
+    fn halve(even: parity::Even) -> i32 {
+        even.halve()
+    }
+
+    #[test]
+    fn parity_types() {
+        let (three, five) = (Odd::try_from(3).unwrap(), Odd::try_from(5).unwrap());
+        let six = parity::Even::try_from(6).unwrap();
+
+        assert_eq!(4, halve(three + five));
+        assert_eq!(-1, halve(three - five));
+        assert_eq!(9, (three + six).get());
+        assert_eq!(15, (three * five).get());
+        assert_eq!(18, halve(six * three * parity::Even::TWO));
+        assert_eq!(-5, (-five).get());
+        assert_eq!(3, halve(six));
+
+        assert_eq!(Parity::Odd(three), Parity::from(3));
+        assert_eq!(Parity::Even(parity::Even::ZERO), Parity::from(0));
+        assert_eq!(Parity::Odd(-Odd::ONE), Parity::from(-1));
+        assert_eq!("4 must be odd", Odd::try_from(4).unwrap_err().to_string());
+        assert_eq!(Err(odd(7)), parity::Even::try_from(7));
+
+        // Both ways between the type-level and the checked representation.
+        let from_refined: parity::Even = even(-8).into();
+        assert_eq!(-8, i32::from(from_refined));
+        assert_eq!(even(6), EvenNumber::from(six));
+        assert_eq!(even(6), EvenNumber::from(three + five) + even(-2));
+        assert_eq!("15", (three * five).to_string());
+    }
+
+    #[test]
+    fn modular_prime() {
+        type Mod7 = ModInt<7>;
+        let three = Mod7::new(3);
+
+        assert_eq!("3 (mod 7)", three.to_string());
+        assert_eq!(Mod7::new(3), Mod7::new(17));
+        assert_eq!(Mod7::new(6), Mod7::from_signed(-1));
+        assert_eq!(Mod7::new(1), three + Mod7::new(5));
+        assert_eq!(Mod7::new(5), three - Mod7::new(5));
+        assert_eq!(Mod7::new(1), three * Mod7::new(5));
+        assert_eq!(Mod7::new(4), -three);
+        assert_eq!(Mod7::ZERO, -Mod7::ZERO);
+
+        // Every non-zero value has an inverse modulo a prime.
+        for value in 1..7 {
+            let value = Mod7::new(value);
+            assert_eq!(Mod7::ONE, value * value.inverse().unwrap());
+            assert_eq!(Mod7::ONE, value.pow(6));
+        }
+        assert_eq!(Mod7::new(5), three.inverse().unwrap());
+        assert_eq!(Mod7::new(2), Mod7::new(6) / three);
+        assert_eq!(Mod7::ONE, three.pow(0));
+        assert_eq!(Mod7::new(3).pow(100), three.pow(100 % 6));
+
+        let mut total = three;
+        total += Mod7::new(6);
+        total *= three;
+        total -= Mod7::ONE;
+        total /= Mod7::new(2);
+        assert_eq!(Mod7::new(6), total);
+
+        // Parity is arithmetic modulo 2.
+        assert_eq!(ModInt::<2>::ZERO, ModInt::<2>::from_signed(-8));
+        assert_eq!(ModInt::<2>::ONE, ModInt::<2>::new(3) * ModInt::new(5));
+    }
+
+    #[test]
+    fn modular_composite() {
+        type Mod12 = ModInt<12>;
+
+        assert_eq!(Mod12::new(5), Mod12::new(5).inverse().unwrap());
+        assert_eq!(Mod12::new(7), Mod12::new(7).inverse().unwrap());
+        assert_eq!(
+            Err(NoInverse {
+                value: 8,
+                modulus: 12,
+                gcd: 4
+            }),
+            Mod12::new(8).inverse()
+        );
+        assert_eq!(
+            "9 has no inverse mod 12: they share the factor 3",
+            Mod12::new(1)
+                .checked_div(Mod12::new(9))
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(Mod12::ZERO.inverse().is_err());
+        assert_eq!(Mod12::ZERO, Mod12::new(4) * Mod12::new(3));
+        assert_eq!(Mod12::new(4), Mod12::new(2).pow(14));
+        assert_eq!(ModInt::<1>::ZERO, ModInt::<1>::ONE);
+    }
+
+    #[test]
+    #[should_panic(expected = "has no inverse")]
+    fn modular_division_without_inverse() {
+        let _ = ModInt::<12>::new(1) / ModInt::new(6);
+    }
+
+    #[test]
+    fn modular_near_overflow() {
+        // The largest prime below 2^64, and 2^64 - 1 = 3 * 5 * 17 * 257 * ...
+        const P: u64 = 18_446_744_073_709_551_557;
+        type Big = ModInt<P>;
+        type Max = ModInt<{ u64::MAX }>;
+
+        let minus_one = Big::new(P - 1);
+        assert_eq!(Big::ONE, minus_one * minus_one);
+        assert_eq!(Big::new(P - 2), minus_one + minus_one);
+        assert_eq!(Big::new(58), Big::new(u64::MAX));
+        assert_eq!(Big::ONE, Big::new(u64::MAX - 1).pow(P - 1));
+        let x = Big::new(0x1234_5678_9abc_def0);
+        assert_eq!(Big::ONE, x * x.inverse().unwrap());
+        assert_eq!(x.inverse().unwrap(), x.pow(P - 2));
+
+        assert_eq!(Max::ONE, Max::new(u64::MAX - 1).pow(2));
+        assert_eq!(Max::ZERO, Max::new(u64::MAX));
+        assert_eq!(
+            Max::new(u64::MAX - 2),
+            Max::new(u64::MAX - 1) + Max::new(u64::MAX - 1)
+        );
+        assert_eq!(Max::new(2), Max::ONE - Max::new(u64::MAX - 1));
+        assert_eq!(Some(5), Max::new(10).inverse().err().map(|error| error.gcd));
+        let y = Max::new(u64::MAX - 1);
+        assert_eq!(Max::ONE, y * y.inverse().unwrap());
+    }
+
+    fn residue(value: u64, modulus: u64) -> Residue {
+        Residue::new(value, modulus).unwrap()
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        // x = 2 (mod 3), x = 3 (mod 5), x = 2 (mod 7)
+        let combined = ModInt::<3>::new(2)
+            .residue()
+            .combine(ModInt::<5>::new(3).residue())
+            .and_then(|residue| residue.combine(ModInt::<7>::new(2).residue()));
+        assert_eq!(Ok(residue(23, 105)), combined);
+        assert_eq!("23 (mod 105)", combined.unwrap().to_string());
+
+        // Moduli sharing a factor work if the residues agree on it.
+        assert_eq!(Ok(residue(10, 12)), crt(&[residue(4, 6), residue(2, 4)]));
+        assert_eq!(
+            Err(CrtError::Incompatible(residue(1, 6), residue(2, 4))),
+            crt(&[residue(1, 6), residue(2, 4)])
+        );
+        assert_eq!(Err(CrtError::Empty), crt(&[]));
+        assert_eq!(None, Residue::new(1, 0));
+        assert_eq!((2, 4), (residue(6, 4).value(), residue(6, 4).modulus()));
+
+        // Large, nearly overflowing moduli.
+        let (p, q) = (4_294_967_291, 4_294_967_279);
+        let x = 12_345_678_901_234_567_890_u64;
+        assert_eq!(Ok(residue(x, p * q)), crt(&[residue(x, p), residue(x, q)]));
+        assert_eq!(
+            Err(CrtError::Overflow),
+            crt(&[residue(1, u64::MAX), residue(1, 2)])
+        );
     }
 }
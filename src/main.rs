@@ -1,15 +1,26 @@
-mod chapters;
-
-use crate::chapters::{chapter_1::chapter_1::{MinMax, Point2D}, chapter_14::generics::{multiple_bounds::consume, the_problem}, chapter_8::closures::{self, closure_as_parameter}};
+use rust_by_examples::chapters::{chapter_1::chapter_1::{MinMax, Point2D}, chapter_14::generics::{multiple_bounds::consume, the_problem}, chapter_8::closures::{self, closure_as_parameter}};
+use rust_by_examples::chapters::chapter_2::chapter_2::representation::Representation;
 fn main() {
-    let minmax = MinMax::new(0, 14);
+    // `cargo run -- explain -5i8` explains how a primitive value is stored
+    // instead of running the demo. Any other arguments are ignored.
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).is_some_and(|command| command == "explain") {
+        match args.get(2).map(|literal| literal.parse::<Representation>()) {
+            Some(Ok(representation)) => println!("{}", representation),
+            Some(Err(err)) => eprintln!("{}", err),
+            None => eprintln!("usage: explain <literal>, e.g. explain -5i8"),
+        }
+        return;
+    }
+
+    let minmax = MinMax::new(0, 14).unwrap();
 
     println!("Compare Structures:");
     println!("Display: {}", minmax);
     println!("Debug: {:?}", minmax);
 
-    let big_range: MinMax = MinMax::new(-300, 300);
-    let small_range: MinMax = MinMax::new(-3, 3);
+    let big_range: MinMax = MinMax::new(-300, 300).unwrap();
+    let small_range: MinMax = MinMax::new(-3, 3).unwrap();
 
     println!(
         "The big range is {big} and the small is {small}",